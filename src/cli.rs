@@ -0,0 +1,329 @@
+//! Command-line entrypoint and subcommands
+//!
+//! Running `skillsync` without arguments starts the daemon. Everything else is
+//! a one-shot command that prints to stdout and exits.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::control::{self, ControlCommand};
+use crate::doctor::{self, Severity};
+use crate::import::{self, ImportAction, ImportFormat};
+use crate::instance::{self, InstanceLock, LockState};
+use crate::lint::{self, Level};
+use crate::project;
+use crate::status::StatusFile;
+
+const USAGE: &str = "\
+Usage: skillsync [COMMAND]
+
+Commands:
+  run                                  Run the sync daemon (default)
+  import <cursor|copilot|codex> [DIR]  Import foreign prompts as Claude skills
+         [--force] [--dry-run]
+  restore --list                       List trash snapshots
+  restore <SNAPSHOT|latest> [SKILL]    Restore removed content from the trash
+  status [--json]                      Show the running daemon's state (--json: last status snapshot)
+  pause | resume                       Stop or restart syncing in the running daemon
+  resync                               Reconcile all destinations now
+  reload-config                        Re-read ~/skillsync/config.toml
+  shutdown                             Stop the running daemon
+  confirm-deletions                    Let the daemon apply deletions held by the safety brake
+  doctor [--fix]                       Check the installation (--fix: repair what is safe to repair)
+  lint [DIR] [--json]                  Validate skills (default: the source directory)
+  help                                 Show this message";
+
+/// Parse command-line arguments and run the selected command
+pub fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    dispatch(&args)
+}
+
+fn dispatch(args: &[String]) -> Result<()> {
+    let Some((command, rest)) = args.split_first() else {
+        return crate::run();
+    };
+
+    match command.as_str() {
+        "run" => crate::run(),
+        "import" => import_command(rest),
+        "restore" => restore_command(rest),
+        "confirm-deletions" => confirm_deletions_command(),
+        "doctor" => doctor_command(rest),
+        "lint" => lint_command(rest),
+        "status" if rest.iter().any(|arg| arg == "--json") => status_json_command(),
+        name @ ("status" | "pause" | "resume" | "resync" | "reload-config" | "shutdown") => {
+            let command = ControlCommand::parse(name).expect("listed control command");
+            control_command(command)
+        }
+        "help" | "-h" | "--help" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        other => bail!("Unknown command '{}'\n\n{}", other, USAGE),
+    }
+}
+
+fn import_command(args: &[String]) -> Result<()> {
+    let mut format = None;
+    let mut dir = None;
+    let mut force = false;
+    let mut dry_run = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--force" => force = true,
+            "--dry-run" => dry_run = true,
+            flag if flag.starts_with('-') => bail!("Unknown flag '{}' for import", flag),
+            value if format.is_none() => format = Some(ImportFormat::parse(value)?),
+            value if dir.is_none() => dir = Some(PathBuf::from(value)),
+            value => bail!("Unexpected argument '{}'", value),
+        }
+    }
+
+    let Some(format) = format else {
+        bail!("Missing import format\n\n{}", USAGE);
+    };
+
+    let config = Config::new()?;
+    let dir = match dir {
+        Some(dir) => dir,
+        None => {
+            let home = home::home_dir().context("Could not determine home directory")?;
+            let cwd = std::env::current_dir().context("Could not determine current directory")?;
+            format.default_dir(&home, &cwd)
+        }
+    };
+
+    let skills = import::discover(format, &dir)?;
+    if skills.is_empty() {
+        println!("No {} prompts found in {}", format.label(), dir.display());
+        return Ok(());
+    }
+
+    let plan = import::plan(format, skills, &config.source, force);
+    println!(
+        "Import preview ({} -> {}):",
+        dir.display(),
+        config.source.display()
+    );
+    for entry in &plan.entries {
+        let action = match entry.action {
+            ImportAction::Create => "create",
+            ImportAction::Overwrite => "overwrite",
+            ImportAction::SkipExisting => "skip (exists, use --force)",
+            ImportAction::SkipDuplicate => "skip (same name as a file above)",
+        };
+        println!(
+            "  {:<28} {:<10} {}",
+            entry.skill.name,
+            action,
+            entry.skill.origin.display()
+        );
+    }
+
+    if dry_run {
+        println!("Dry run: nothing written");
+        return Ok(());
+    }
+
+    let trash = config.trash.enabled.then(|| config.trash_store());
+    let written = import::apply(&plan, trash.as_ref())?;
+    let skipped = plan.entries.len() - written;
+    println!("Imported {} skill(s), skipped {}", written, skipped);
+    Ok(())
+}
+
+fn control_command(command: ControlCommand) -> Result<()> {
+    let config = Config::new()?;
+    let output = control::send(&config.control_socket(), command)?;
+    print!("{}", output);
+    Ok(())
+}
+
+fn status_json_command() -> Result<()> {
+    let config = Config::new()?;
+    let status = StatusFile::read(&config.status_file())?;
+    println!("{}", serde_json::to_string_pretty(&status)?);
+    Ok(())
+}
+
+fn confirm_deletions_command() -> Result<()> {
+    let config = Config::new()?;
+    if let Ok(output) = control::send(&config.control_socket(), ControlCommand::ConfirmDeletions) {
+        print!("{}", output);
+        return Ok(());
+    }
+
+    // No daemon to talk to: leave a marker it applies once it is running
+    let marker = config.confirm_deletions_file();
+    fs::create_dir_all(&config.state_dir)
+        .with_context(|| format!("Failed to create {}", config.state_dir.display()))?;
+    fs::write(&marker, "").with_context(|| format!("Failed to write {}", marker.display()))?;
+    println!("Daemon not reachable; held deletions will be applied when it next runs");
+    Ok(())
+}
+
+fn doctor_command(args: &[String]) -> Result<()> {
+    let fix = match args {
+        [] => false,
+        [flag] if flag == "--fix" => true,
+        _ => bail!("Usage: skillsync doctor [--fix]"),
+    };
+    let home = home::home_dir().context("Could not determine home directory")?;
+
+    let mut checks = doctor::diagnose(&home);
+    if fix {
+        let mut applied = 0;
+        for check in &checks {
+            let Some(repair) = check
+                .fix
+                .as_ref()
+                .filter(|_| check.severity != Severity::Ok)
+            else {
+                continue;
+            };
+            match repair.apply() {
+                Ok(done) => {
+                    println!("fixed: {}", done);
+                    applied += 1;
+                }
+                Err(e) => println!("could not fix {}: {:#}", check.name, e),
+            }
+        }
+        if applied > 0 {
+            println!();
+            checks = doctor::diagnose(&home);
+        }
+    }
+
+    for check in &checks {
+        let label = match check.severity {
+            Severity::Ok => " ok ",
+            Severity::Warn => "warn",
+            Severity::Fail => "FAIL",
+        };
+        println!("[{}] {:<12} {}", label, check.name, check.detail);
+        if check.severity != Severity::Ok {
+            if let Some(remedy) = &check.remedy {
+                for line in remedy.lines() {
+                    println!("       {:<12} {}", "", line);
+                }
+            }
+        }
+    }
+
+    let failed = checks
+        .iter()
+        .filter(|c| c.severity == Severity::Fail)
+        .count();
+    let warned = checks
+        .iter()
+        .filter(|c| c.severity == Severity::Warn)
+        .count();
+    if failed > 0 {
+        bail!("{} check(s) failed, {} warning(s)", failed, warned);
+    }
+    println!("\n{} warning(s)", warned);
+    Ok(())
+}
+
+fn lint_command(args: &[String]) -> Result<()> {
+    let mut json = false;
+    let mut dir = None;
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            flag if flag.starts_with('-') => bail!("Unknown flag '{}' for lint", flag),
+            value if dir.is_none() => dir = Some(PathBuf::from(value)),
+            value => bail!("Unexpected argument '{}'", value),
+        }
+    }
+
+    let config = Config::new()?;
+    let sources = match dir {
+        Some(dir) => vec![dir],
+        None => config.sources.clone(),
+    };
+    let report = lint::lint_sources(&sources, &config.destinations, &config.home)?;
+    let errors = report.count(Level::Error);
+    let warnings = report.count(Level::Warning);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        for finding in &report.findings {
+            let label = match finding.level {
+                Level::Warning => "warning",
+                Level::Error => "error",
+            };
+            println!(
+                "{}: {} [{}] {}",
+                label,
+                finding.path.display(),
+                finding.rule,
+                finding.message
+            );
+        }
+        println!(
+            "{} skill(s) checked: {} error(s), {} warning(s)",
+            report.skills, errors, warnings
+        );
+    }
+
+    if errors > 0 {
+        bail!("lint found {} error(s)", errors);
+    }
+    Ok(())
+}
+
+fn restore_command(args: &[String]) -> Result<()> {
+    let config = Config::new()?;
+    let trash = config.trash_store();
+
+    match args {
+        [flag] if flag == "--list" => {
+            let snapshots = trash.snapshots()?;
+            if snapshots.is_empty() {
+                println!("Trash is empty ({})", trash.root().display());
+            }
+            for snapshot in snapshots {
+                println!("{}  {} item(s)", snapshot.id, snapshot.entries.len());
+                for entry in &snapshot.entries {
+                    println!("    {}", entry.display());
+                }
+            }
+            Ok(())
+        }
+        [id] | [id, _] if !id.starts_with('-') => {
+            let skill = args.get(1).map(String::as_str);
+            // A running daemon does the restore itself so it never sees
+            // half-restored destinations
+            let _lock = match InstanceLock::acquire(&config.lock_file())? {
+                LockState::Acquired(lock) => lock,
+                LockState::Held(pid) => {
+                    let command = ControlCommand::Restore {
+                        snapshot: id.clone(),
+                        skill: skill.map(str::to_string),
+                    };
+                    let output =
+                        control::send(&config.control_socket(), command).with_context(|| {
+                            format!(
+                                "skillsync is running ({}) but did not take the restore",
+                                instance::describe_holder(pid)
+                            )
+                        })?;
+                    print!("{}", output);
+                    return Ok(());
+                }
+            };
+            let restored = trash.restore_snapshot(id, skill, &project::mirrors(&config))?;
+            project::adopt_restored(&config, &restored);
+            print!("{}", restored.report());
+            Ok(())
+        }
+        _ => bail!("Usage: skillsync restore --list | <SNAPSHOT|latest> [SKILL]"),
+    }
+}
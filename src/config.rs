@@ -1,51 +1,525 @@
 //! Configuration and path management
 
-use anyhow::{Context, Result};
-use std::path::PathBuf;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use crate::destination::{Destination, DestinationType};
+use crate::inline::InlineSettings;
+use crate::preflight;
+use crate::safety::DeletionGuard;
+use crate::transform::FieldMap;
+use crate::trash::Trash;
 
+#[derive(Debug, Clone)]
 pub struct Config {
+    /// Home directory everything else is derived from, kept for reloads
+    pub home: PathBuf,
+    /// Highest-precedence source (by default Claude's own skills
+    /// directory); imports and restores write here
     pub source: PathBuf,
+    /// Every source synced from, highest precedence first
+    pub sources: Vec<PathBuf>,
     pub destinations: Vec<Destination>,
     pub log_dir: PathBuf,
+    /// Runtime state (trash, status) lives here
+    pub state_dir: PathBuf,
+    /// Optional user settings file; defaults apply when it is missing
+    pub config_file: PathBuf,
+    pub trash: TrashSettings,
+    pub safety: SafetySettings,
+    pub watcher: WatcherSettings,
+    pub reload: ReloadSettings,
+    pub tools: ToolSettings,
+    pub projects: ProjectSettings,
+}
+
+/// Settings read from `~/skillsync/config.toml`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileConfig {
+    trash: TrashSettings,
+    safety: SafetySettings,
+    watcher: WatcherSettings,
+    reload: ReloadSettings,
+    tools: ToolSettings,
+    projects: ProjectsEntry,
+    /// Replaces the built-in `~/.claude/skills` source when present
+    sources: Option<Vec<SourceEntry>>,
+    /// Replaces the built-in destination list when present
+    destinations: Option<Vec<DestinationEntry>>,
+}
+
+/// One `[[sources]]` table
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SourceEntry {
+    /// Absolute, or relative to the home directory with a leading `~/`
+    path: String,
+    /// Skills in higher-priority sources override same-named ones; ties go
+    /// to the source listed first
+    #[serde(default)]
+    priority: i64,
+}
+
+/// One `[[destinations]]` table
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DestinationEntry {
+    /// Absolute, or relative to the home directory with a leading `~/`
+    path: String,
+    #[serde(rename = "type")]
+    dest_type: DestinationType,
+    /// Tool id from the registry, e.g. `gemini`; inferred from the path if omitted
+    tool: Option<String>,
+    /// Frontmatter key -> key written to this destination (`""` drops it),
+    /// and an optional `keep` list of the only keys written
+    #[serde(default)]
+    fields: FieldMap,
+    /// Inline referenced files into prompts (gemini-toml only)
+    inline: Option<InlineSettings>,
+    /// Point source-root paths and `${CLAUDE_SKILL_DIR}` at this destination
+    #[serde(default)]
+    rewrite_paths: bool,
+}
+
+/// The `[projects]` table
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ProjectsEntry {
+    /// Directories searched for repositories with `.claude/skills`
+    workspaces: Vec<String>,
+    /// Project roots synced whether or not they are under a workspace
+    paths: Vec<String>,
+    max_depth: usize,
+    rescan_interval_secs: u64,
+    /// Replaces the built-in project destinations; paths relative to the project
+    destinations: Option<Vec<DestinationEntry>>,
+}
+
+impl Default for ProjectsEntry {
+    fn default() -> Self {
+        Self {
+            workspaces: Vec::new(),
+            paths: Vec::new(),
+            max_depth: 3,
+            rescan_interval_secs: 60,
+            destinations: None,
+        }
+    }
+}
+
+/// Project-local sync: each project's `.claude/skills` mirrored inside it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectSettings {
+    pub workspaces: Vec<PathBuf>,
+    pub paths: Vec<PathBuf>,
+    /// How many directory levels below a workspace are searched
+    pub max_depth: usize,
+    /// Seconds between searches for added or removed projects
+    pub rescan_interval_secs: u64,
+    /// Destinations with `base_path` relative to the project root
+    pub destinations: Vec<Destination>,
+}
+
+impl ProjectSettings {
+    /// Whether any project can be synced at all
+    pub fn enabled(&self) -> bool {
+        !self.workspaces.is_empty() || !self.paths.is_empty()
+    }
+
+    pub fn rescan_interval(&self) -> Duration {
+        Duration::from_secs(self.rescan_interval_secs.max(1))
+    }
+}
+
+/// Where removed destination content goes and how long it is kept
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TrashSettings {
+    /// Move removed files into the trash instead of deleting them
+    pub enabled: bool,
+    /// Keep at most this many snapshots
+    pub max_snapshots: usize,
+    /// Drop snapshots older than this many days
+    pub max_age_days: u64,
+}
+
+impl Default for TrashSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_snapshots: 50,
+            max_age_days: 30,
+        }
+    }
+}
+
+impl TrashSettings {
+    pub fn max_age(&self) -> Duration {
+        Duration::from_secs(self.max_age_days * 24 * 60 * 60)
+    }
+}
+
+/// Limits for the mass-deletion brake
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SafetySettings {
+    /// Hold a pass that would remove more than this fraction of skills
+    pub max_delete_fraction: f64,
+    /// Hold a pass that would remove more than this many skills
+    pub max_delete_count: usize,
+    /// Removals within this many seconds count toward the limits together
+    pub delete_window_secs: u64,
+}
+
+impl Default for SafetySettings {
+    fn default() -> Self {
+        Self {
+            max_delete_fraction: 0.5,
+            max_delete_count: 20,
+            delete_window_secs: 60,
+        }
+    }
+}
+
+/// Event loop tuning
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WatcherSettings {
+    /// Window in which raw file events are merged into one batch
+    pub debounce_ms: u64,
+    /// Only render a skill once its files have been quiet this long; 0 disables
+    pub settle_ms: u64,
+    /// Seconds between full reconciliation passes; 0 disables them
+    pub reconcile_interval_secs: u64,
+}
+
+impl Default for WatcherSettings {
+    fn default() -> Self {
+        Self {
+            debounce_ms: 100,
+            settle_ms: 0,
+            reconcile_interval_secs: 15 * 60,
+        }
+    }
+}
+
+impl WatcherSettings {
+    /// Re-read just the `[watcher]` section, for hot reload
+    pub fn load(config_file: &Path) -> Result<Self> {
+        Ok(load_file_config(config_file)?.watcher)
+    }
+
+    pub fn debounce(&self) -> Duration {
+        Duration::from_millis(self.debounce_ms.max(1))
+    }
+
+    pub fn settle(&self) -> Duration {
+        Duration::from_millis(self.settle_ms)
+    }
+
+    pub fn reconcile_interval(&self) -> Option<Duration> {
+        (self.reconcile_interval_secs > 0)
+            .then(|| Duration::from_secs(self.reconcile_interval_secs))
+    }
+}
+
+/// What happens when the config file changes under a running daemon
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReloadSettings {
+    /// Discard synced skills from destinations dropped from the config
+    pub clean_removed_destinations: bool,
+}
+
+/// What to do with destinations whose tool is not installed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MissingToolPolicy {
+    /// Skip the destination until the tool shows up
+    Disable,
+    /// Keep syncing and log a warning
+    Warn,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ToolSettings {
+    pub on_missing: MissingToolPolicy,
+    /// Seconds between checks for newly installed tools
+    pub recheck_interval_secs: u64,
+}
+
+impl Default for ToolSettings {
+    fn default() -> Self {
+        Self {
+            on_missing: MissingToolPolicy::Disable,
+            recheck_interval_secs: 5 * 60,
+        }
+    }
+}
+
+impl ToolSettings {
+    pub fn recheck_interval(&self) -> Duration {
+        Duration::from_secs(self.recheck_interval_secs.max(1))
+    }
 }
 
 impl Config {
     pub fn new() -> Result<Self> {
         let home = home::home_dir().context("Could not determine home directory")?;
+        Self::for_home(&home)
+    }
+
+    /// Build the configuration rooted at `home`, applying `~/skillsync/config.toml` if present
+    pub fn for_home(home: &Path) -> Result<Self> {
+        let config_file = home.join("skillsync").join("config.toml");
+        Self::with_file_config(home, load_file_config(&config_file)?)
+    }
+
+    /// The configuration `home` would have without a config file
+    pub fn builtin(home: &Path) -> Result<Self> {
+        Self::with_file_config(home, FileConfig::default())
+    }
+
+    fn with_file_config(home: &Path, file_config: FileConfig) -> Result<Self> {
+        let app_dir = home.join("skillsync");
+        let log_dir = app_dir.join("logs");
+        let state_dir = app_dir.join("state");
+        let config_file = app_dir.join("config.toml");
+
+        let sources = match &file_config.sources {
+            Some(entries) => {
+                if entries.is_empty() {
+                    bail!("{} lists no sources", config_file.display());
+                }
+                let mut entries: Vec<&SourceEntry> = entries.iter().collect();
+                entries.sort_by_key(|entry| std::cmp::Reverse(entry.priority));
+                entries
+                    .iter()
+                    .map(|entry| expand_home(home, &entry.path))
+                    .collect::<Result<Vec<_>>>()
+                    .with_context(|| format!("Invalid sources in {}", config_file.display()))?
+            }
+            None => vec![home.join(".claude").join("skills")],
+        };
+        let source = sources[0].clone();
+
+        let destinations = match &file_config.destinations {
+            Some(entries) => entries
+                .iter()
+                .map(|entry| destination(entry, expand_home(home, &entry.path)?, home))
+                .collect::<Result<Vec<_>>>()
+                .with_context(|| format!("Invalid destinations in {}", config_file.display()))?,
+            None => default_destinations(home),
+        };
 
-        let source = home.join(".claude").join("skills");
-        let log_dir = home.join("skillsync").join("logs");
-
-        // Configure destinations with their types
-        let destinations = vec![
-            Destination::new(
-                home.join(".gemini").join("skills"),
-                DestinationType::ClaudeStyle,
-            ),
-            Destination::new(
-                home.join(".gemini").join("antigravity").join("skills"),
-                DestinationType::ClaudeStyle,
-            ),
-            Destination::new(
-                home.join(".codex").join("skills"),
-                DestinationType::ClaudeStyle,
-            ),
-            Destination::new(
-                home.join(".cursor").join("skills"),
-                DestinationType::ClaudeStyle,
-            ),
-            Destination::new(
-                home.join(".gemini").join("commands"),
-                DestinationType::GeminiToml,
-            ),
-        ];
+        let projects = &file_config.projects;
+        let expand_all = |paths: &[String]| {
+            paths
+                .iter()
+                .map(|path| expand_home(home, path))
+                .collect::<Result<Vec<_>>>()
+        };
+        let projects = ProjectSettings {
+            workspaces: expand_all(&projects.workspaces)
+                .with_context(|| format!("Invalid projects in {}", config_file.display()))?,
+            paths: expand_all(&projects.paths)
+                .with_context(|| format!("Invalid projects in {}", config_file.display()))?,
+            max_depth: projects.max_depth,
+            rescan_interval_secs: projects.rescan_interval_secs,
+            destinations: match &projects.destinations {
+                Some(entries) => entries
+                    .iter()
+                    .map(|entry| {
+                        let path = PathBuf::from(&entry.path);
+                        if path.is_absolute() || entry.path.starts_with('~') {
+                            bail!(
+                                "project destination '{}' must be relative to the project",
+                                entry.path
+                            );
+                        }
+                        // Tools are inferred from paths relative to the project
+                        destination(entry, path, Path::new(""))
+                    })
+                    .collect::<Result<Vec<_>>>()
+                    .with_context(|| {
+                        format!("Invalid project destinations in {}", config_file.display())
+                    })?,
+                None => default_project_destinations(),
+            },
+        };
+
+        // Keeping no snapshots would prune each one right after it was taken
+        if file_config.trash.enabled && file_config.trash.max_snapshots == 0 {
+            bail!(
+                "{}: [trash] max_snapshots must be at least 1; set enabled = false to disable the trash",
+                config_file.display()
+            );
+        }
 
         Ok(Self {
+            home: home.to_path_buf(),
             source,
+            sources,
             destinations,
             log_dir,
+            state_dir,
+            config_file,
+            trash: file_config.trash,
+            safety: file_config.safety,
+            watcher: file_config.watcher,
+            reload: file_config.reload,
+            tools: file_config.tools,
+            projects,
         })
     }
+
+    /// Read the configuration again for the same home directory
+    pub fn reload(&self) -> Result<Self> {
+        Self::for_home(&self.home)
+    }
+
+    /// JSON snapshot of the daemon's health
+    pub fn status_file(&self) -> PathBuf {
+        self.state_dir.join("status.json")
+    }
+
+    /// Lock held by the running daemon, containing its PID
+    pub fn lock_file(&self) -> PathBuf {
+        self.state_dir.join("skillsync.pid")
+    }
+
+    /// Unix socket the running daemon listens on
+    pub fn control_socket(&self) -> PathBuf {
+        self.state_dir.join("skillsync.sock")
+    }
+
+    /// Marker file that confirms held deletions
+    pub fn confirm_deletions_file(&self) -> PathBuf {
+        self.state_dir.join("confirm-deletions")
+    }
+
+    pub fn deletion_guard(&self) -> DeletionGuard {
+        DeletionGuard {
+            max_fraction: self.safety.max_delete_fraction,
+            max_count: self.safety.max_delete_count,
+            confirm_file: self.confirm_deletions_file(),
+            window: Duration::from_secs(self.safety.delete_window_secs),
+        }
+    }
+
+    pub fn trash_store(&self) -> Trash {
+        Trash::new(
+            self.state_dir.join("trash"),
+            self.trash.max_snapshots,
+            self.trash.max_age(),
+        )
+    }
+}
+
+/// A destination at `base_path` as a `[[destinations]]` table describes it
+///
+/// Without an explicit `tool`, it is inferred from `base_path` relative to `root`.
+fn destination(entry: &DestinationEntry, base_path: PathBuf, root: &Path) -> Result<Destination> {
+    let mut dest = Destination::new(base_path, entry.dest_type.clone());
+    if let Some(tool) = &entry.tool {
+        if preflight::tool(tool).is_none() {
+            bail!("unknown tool '{}' for destination {}", tool, entry.path);
+        }
+        dest = dest.with_tool(tool);
+    } else if let Some(spec) = preflight::tool_for(root, &dest) {
+        dest = dest.with_tool(spec.id);
+    }
+    if let Some(settings) = &entry.inline {
+        if entry.dest_type != DestinationType::GeminiToml {
+            bail!("inline on {} needs type = \"gemini-toml\"", entry.path);
+        }
+        dest = dest.with_inline(settings.clone());
+    }
+    if entry.rewrite_paths {
+        dest = dest.with_path_rewriting();
+    }
+    Ok(dest.with_fields(entry.fields.clone()))
+}
+
+/// Destinations used when the config file does not list any
+fn default_destinations(home: &Path) -> Vec<Destination> {
+    vec![
+        Destination::new(
+            home.join(".gemini").join("skills"),
+            DestinationType::ClaudeStyle,
+        )
+        .with_tool("gemini"),
+        Destination::new(
+            home.join(".gemini").join("antigravity").join("skills"),
+            DestinationType::ClaudeStyle,
+        )
+        .with_tool("antigravity"),
+        Destination::new(
+            home.join(".codex").join("skills"),
+            DestinationType::ClaudeStyle,
+        )
+        .with_tool("codex"),
+        Destination::new(
+            home.join(".cursor").join("skills"),
+            DestinationType::ClaudeStyle,
+        )
+        .with_tool("cursor"),
+        Destination::new(
+            home.join(".gemini").join("commands"),
+            DestinationType::GeminiToml,
+        )
+        .with_tool("gemini"),
+    ]
+}
+
+/// Project destinations used when `[projects]` does not list any, relative
+/// to the project root
+fn default_project_destinations() -> Vec<Destination> {
+    vec![
+        Destination::new(
+            PathBuf::from(".gemini").join("skills"),
+            DestinationType::ClaudeStyle,
+        )
+        .with_tool("gemini"),
+        Destination::new(
+            PathBuf::from(".codex").join("skills"),
+            DestinationType::ClaudeStyle,
+        )
+        .with_tool("codex"),
+        Destination::new(
+            PathBuf::from(".cursor").join("skills"),
+            DestinationType::ClaudeStyle,
+        )
+        .with_tool("cursor"),
+        Destination::new(
+            PathBuf::from(".gemini").join("commands"),
+            DestinationType::GeminiToml,
+        )
+        .with_tool("gemini"),
+    ]
+}
+
+fn expand_home(home: &Path, path: &str) -> Result<PathBuf> {
+    let expanded = match path.strip_prefix("~/") {
+        Some(rest) => home.join(rest),
+        None => PathBuf::from(path),
+    };
+    if !expanded.is_absolute() {
+        bail!("path '{}' must be absolute or start with ~/", path);
+    }
+    Ok(expanded)
+}
+
+fn load_file_config(path: &Path) -> Result<FileConfig> {
+    if !path.exists() {
+        return Ok(FileConfig::default());
+    }
+    let text =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    toml::from_str(&text).with_context(|| format!("Invalid config file {}", path.display()))
 }
@@ -0,0 +1,241 @@
+//! Unix domain socket for controlling a running daemon
+//!
+//! The protocol is one command per connection: the client writes a command
+//! name and its space-separated arguments terminated by a newline, the daemon answers with `ok` or
+//! `error <message>` on the first line followed by any output, then closes.
+//! Arguments containing whitespace, quotes or backslashes are sent in double
+//! quotes with `\"`, `\\` and `\n` escapes.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::sync::mpsc::{channel, Sender};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// How long a client waits for the daemon to answer
+const REPLY_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlCommand {
+    Status,
+    Pause,
+    Resume,
+    Resync,
+    ReloadConfig,
+    ConfirmDeletions,
+    Shutdown,
+    /// Restore trash content on behalf of a `skillsync restore` run
+    Restore {
+        snapshot: String,
+        skill: Option<String>,
+    },
+}
+
+impl ControlCommand {
+    pub fn parse(line: &str) -> Option<Self> {
+        let words = split_args(line)?;
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        match words.as_slice() {
+            ["restore", snapshot] => Some(Self::Restore {
+                snapshot: snapshot.to_string(),
+                skill: None,
+            }),
+            ["restore", snapshot, skill] => Some(Self::Restore {
+                snapshot: snapshot.to_string(),
+                skill: Some(skill.to_string()),
+            }),
+            [name] => Self::parse_name(name),
+            _ => None,
+        }
+    }
+
+    fn parse_name(name: &str) -> Option<Self> {
+        match name {
+            "status" => Some(Self::Status),
+            "pause" => Some(Self::Pause),
+            "resume" => Some(Self::Resume),
+            "resync" => Some(Self::Resync),
+            "reload-config" => Some(Self::ReloadConfig),
+            "confirm-deletions" => Some(Self::ConfirmDeletions),
+            "shutdown" => Some(Self::Shutdown),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Status => "status",
+            Self::Pause => "pause",
+            Self::Resume => "resume",
+            Self::Resync => "resync",
+            Self::ReloadConfig => "reload-config",
+            Self::ConfirmDeletions => "confirm-deletions",
+            Self::Shutdown => "shutdown",
+            Self::Restore { .. } => "restore",
+        }
+    }
+
+    /// The request line sent over the socket
+    pub fn to_line(&self) -> String {
+        match self {
+            Self::Restore { snapshot, skill } => match skill {
+                Some(skill) => format!("restore {} {}", quote(snapshot), quote(skill)),
+                None => format!("restore {}", quote(snapshot)),
+            },
+            other => other.name().to_string(),
+        }
+    }
+}
+
+/// `arg` as one word of a request line
+fn quote(arg: &str) -> String {
+    let plain =
+        !arg.is_empty() && !arg.contains(|c: char| c.is_whitespace() || c == '"' || c == '\\');
+    if plain {
+        return arg.to_string();
+    }
+    let mut quoted = String::from('"');
+    for c in arg.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Words of a request line, undoing [`quote`]; `None` if a quote is unterminated
+fn split_args(line: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let Some(first) = chars.next() else {
+            return Some(words);
+        };
+        let mut word = String::new();
+        if first == '"' {
+            loop {
+                match chars.next()? {
+                    '"' => break,
+                    '\\' => match chars.next()? {
+                        'n' => word.push('\n'),
+                        c => word.push(c),
+                    },
+                    c => word.push(c),
+                }
+            }
+        } else {
+            word.push(first);
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                word.push(c);
+            }
+        }
+        words.push(word);
+    }
+}
+
+/// A command received on the socket, answered through `reply`
+pub struct ControlRequest {
+    pub command: ControlCommand,
+    pub reply: Sender<Result<String, String>>,
+}
+
+/// Bind the control socket and hand every request to `dispatch` on a background thread
+pub fn serve<F>(socket_path: &Path, dispatch: F) -> Result<()>
+where
+    F: Fn(ControlRequest) -> bool + Send + 'static,
+{
+    if let Some(parent) = socket_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    // A socket left behind by a crashed daemon blocks bind()
+    if socket_path.exists() {
+        fs::remove_file(socket_path)
+            .with_context(|| format!("Failed to remove stale {}", socket_path.display()))?;
+    }
+
+    let listener = UnixListener::bind(socket_path)
+        .with_context(|| format!("Failed to bind {}", socket_path.display()))?;
+    fs::set_permissions(socket_path, fs::Permissions::from_mode(0o600))?;
+    info!(path = %socket_path.display(), "control socket listening");
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    warn!(error = %e, "control socket accept failed");
+                    continue;
+                }
+            };
+            if let Err(e) = handle_connection(stream, &dispatch) {
+                warn!(error = %e, "control connection failed");
+            }
+        }
+    });
+
+    Ok(())
+}
+
+fn handle_connection<F>(stream: UnixStream, dispatch: &F) -> Result<()>
+where
+    F: Fn(ControlRequest) -> bool,
+{
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    let line = line.trim();
+
+    let response = match ControlCommand::parse(line) {
+        None => Err(format!("unknown command '{}'", line)),
+        Some(command) => {
+            let (reply, answer) = channel();
+            if dispatch(ControlRequest { command, reply }) {
+                answer
+                    .recv_timeout(REPLY_TIMEOUT)
+                    .unwrap_or_else(|_| Err("daemon did not answer".to_string()))
+            } else {
+                Err("daemon is shutting down".to_string())
+            }
+        }
+    };
+
+    let mut stream = stream;
+    match response {
+        Ok(output) => write!(stream, "ok\n{}", output)?,
+        Err(message) => writeln!(stream, "error {}", message)?,
+    }
+    Ok(())
+}
+
+/// Send `command` to the daemon listening on `socket_path` and return its output
+pub fn send(socket_path: &Path, command: ControlCommand) -> Result<String> {
+    let mut stream = UnixStream::connect(socket_path).with_context(|| {
+        format!(
+            "Could not reach the skillsync daemon at {} (is it running?)",
+            socket_path.display()
+        )
+    })?;
+    stream.set_read_timeout(Some(REPLY_TIMEOUT))?;
+    writeln!(stream, "{}", command.to_line())?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let (status, output) = response.split_once('\n').unwrap_or((&response, ""));
+    if let Some(message) = status.strip_prefix("error ") {
+        bail!("daemon: {}", message);
+    }
+    if status != "ok" {
+        bail!("Unexpected reply from daemon: {}", status);
+    }
+    Ok(output.to_string())
+}
@@ -1,8 +1,14 @@
 //! Destination types and configuration
 
+use serde::Deserialize;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone)]
+use crate::inline::InlineSettings;
+use crate::transform::FieldMap;
+use crate::version::{Version, VersionRange};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum DestinationType {
     /// Direct copy preserving directory structure and YAML frontmatter
     ClaudeStyle,
@@ -10,10 +16,19 @@ pub enum DestinationType {
     GeminiToml,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Destination {
     pub base_path: PathBuf,
     pub dest_type: DestinationType,
+    /// Id of the tool in `preflight::TOOLS` this destination is for; when
+    /// unset it is inferred from the path
+    pub tool: Option<String>,
+    /// Frontmatter keys kept, renamed or dropped when rendering for this destination
+    pub fields: FieldMap,
+    /// Inline referenced files into the prompt; Gemini TOML destinations only
+    pub inline: Option<InlineSettings>,
+    /// Point source paths in skills at this destination (`rewrite::PathRewrite`)
+    pub rewrite_paths: bool,
 }
 
 impl Destination {
@@ -21,6 +36,72 @@ impl Destination {
         Self {
             base_path,
             dest_type,
+            tool: None,
+            fields: FieldMap::new(),
+            inline: None,
+            rewrite_paths: false,
         }
     }
+
+    pub fn with_tool(mut self, tool: &str) -> Self {
+        self.tool = Some(tool.to_string());
+        self
+    }
+
+    pub fn with_fields(mut self, fields: FieldMap) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    pub fn with_inline(mut self, settings: InlineSettings) -> Self {
+        self.inline = Some(settings);
+        self
+    }
+
+    pub fn with_path_rewriting(mut self) -> Self {
+        self.rewrite_paths = true;
+        self
+    }
+}
+
+/// Tool versions a renderer's output is known to work with
+#[derive(Debug)]
+pub struct Support {
+    pub tool: &'static str,
+    pub dest_type: DestinationType,
+    pub range: VersionRange,
+}
+
+/// Declared compatibility of each renderer
+///
+/// A tool and renderer pair without an entry has no known version limit:
+/// every version is supported and never warned about. That is the case for
+/// Gemini TOML commands and for Claude-style output to Codex, Cursor and
+/// Antigravity.
+pub const SUPPORT: &[Support] = &[Support {
+    // Gemini CLI reads SKILL.md directories from ~/.gemini/skills since 0.23
+    tool: "gemini",
+    dest_type: DestinationType::ClaudeStyle,
+    range: VersionRange {
+        min: Some(Version::new(0, 23, 0)),
+        below: None,
+    },
+}];
+
+impl DestinationType {
+    /// Name as written in `config.toml`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DestinationType::ClaudeStyle => "claude-style",
+            DestinationType::GeminiToml => "gemini-toml",
+        }
+    }
+
+    /// How this renderer's output depends on `tool`'s version; `None` when
+    /// every version is supported
+    pub fn support(&self, tool: &str) -> Option<&'static Support> {
+        SUPPORT
+            .iter()
+            .find(|support| support.tool == tool && support.dest_type == *self)
+    }
 }
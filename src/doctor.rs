@@ -0,0 +1,577 @@
+//! `skillsync doctor`: environment diagnostics with optional repairs
+//!
+//! Every check produces a [`Check`] with a verdict, a one-line detail and,
+//! for problems, a remedy. Problems that are safe to repair automatically
+//! carry a [`Fix`] that `skillsync doctor --fix` applies.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::config::{Config, MissingToolPolicy};
+use crate::instance;
+use crate::preflight::{self, VersionProbe};
+use crate::sync::SkillSync;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Ok,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub name: String,
+    pub severity: Severity,
+    pub detail: String,
+    /// What the user can do about a warning or failure
+    pub remedy: Option<String>,
+    /// Repair `--fix` can apply
+    pub fix: Option<Fix>,
+}
+
+impl Check {
+    fn ok(name: &str, detail: String) -> Self {
+        Self {
+            name: name.to_string(),
+            severity: Severity::Ok,
+            detail,
+            remedy: None,
+            fix: None,
+        }
+    }
+
+    fn problem(name: &str, severity: Severity, detail: String, remedy: String) -> Self {
+        Self {
+            name: name.to_string(),
+            severity,
+            detail,
+            remedy: Some(remedy),
+            fix: None,
+        }
+    }
+
+    fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fix {
+    CreateDir(PathBuf),
+    /// Give the owner read, write and search permission on a directory
+    OwnerAccess(PathBuf),
+    /// Remove a file left behind by a daemon that is no longer running
+    RemoveStale(PathBuf),
+    /// Write the launchd/systemd service definition
+    InstallService(PathBuf),
+}
+
+impl Fix {
+    /// Apply the repair and describe what was done
+    pub fn apply(&self) -> Result<String> {
+        match self {
+            Self::CreateDir(path) => {
+                fs::create_dir_all(path)
+                    .with_context(|| format!("Failed to create {}", path.display()))?;
+                Ok(format!("created {}", path.display()))
+            }
+            Self::OwnerAccess(path) => {
+                let mode = fs::metadata(path)?.permissions().mode() | 0o700;
+                fs::set_permissions(path, fs::Permissions::from_mode(mode))
+                    .with_context(|| format!("Failed to change mode of {}", path.display()))?;
+                Ok(format!(
+                    "set mode {:o} on {}",
+                    mode & 0o7777,
+                    path.display()
+                ))
+            }
+            Self::RemoveStale(path) => {
+                fs::remove_file(path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+                Ok(format!("removed {}", path.display()))
+            }
+            Self::InstallService(path) => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                let exe = std::env::current_exe().context("Could not locate skillsync binary")?;
+                let path_env = std::env::var("PATH").unwrap_or_default();
+                fs::write(path, service_definition(&exe, &path_env))
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                Ok(format!(
+                    "wrote {}; load it with: {}",
+                    path.display(),
+                    service_load_hint(path)
+                ))
+            }
+        }
+    }
+}
+
+/// What `diagnose` asks of the system beyond the home directory
+pub struct Probes {
+    /// Locate each tool's binary and version
+    pub tools: fn(&Path) -> preflight::PreflightOutcome,
+    /// Whether the service manager knows the service; `None` when it cannot be asked
+    pub service_loaded: fn() -> Option<bool>,
+}
+
+impl Probes {
+    /// Run the tools' binaries and ask launchd/systemd
+    pub fn system() -> Self {
+        Self {
+            tools: |home| preflight::probe_all(home, &mut VersionProbe::default()),
+            service_loaded,
+        }
+    }
+}
+
+/// Run every check for the installation rooted at `home`
+pub fn diagnose(home: &Path) -> Vec<Check> {
+    diagnose_with(home, &Probes::system())
+}
+
+/// [`diagnose`] with the given system probes
+pub fn diagnose_with(home: &Path, probes: &Probes) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    let config = match Config::for_home(home) {
+        Ok(config) => {
+            let detail = if config.config_file.exists() {
+                format!("{} is valid", config.config_file.display())
+            } else {
+                format!(
+                    "{} not present, using defaults",
+                    config.config_file.display()
+                )
+            };
+            checks.push(Check::ok("config", detail));
+            config
+        }
+        Err(e) => {
+            checks.push(Check::problem(
+                "config",
+                Severity::Fail,
+                format!("{:#}", e),
+                "Fix the file or move it aside; the remaining checks use the defaults".to_string(),
+            ));
+            match Config::builtin(home) {
+                Ok(config) => config,
+                Err(_) => return checks,
+            }
+        }
+    };
+
+    let outcome = (probes.tools)(&config.home);
+    check_tools(&config, &outcome, &mut checks);
+
+    for source in &config.sources {
+        checks.push(check_dir("source", source, false));
+    }
+    for dest in &config.destinations {
+        checks.push(check_dir("destination", &dest.base_path, true));
+    }
+    checks.push(check_dir("state", &config.state_dir, true));
+    checks.push(check_dir("logs", &config.log_dir, true));
+
+    let running = check_daemon(&config, &mut checks);
+    checks.push(check_service(home, running, probes));
+
+    // Orphans are only meaningful against the complete merged tree
+    if config.sources.iter().all(|source| source.is_dir()) {
+        let (destinations, _) = preflight::active_destinations(&config, &outcome);
+        let sync = SkillSync::new(config.source.clone(), destinations)
+            .with_sources(config.sources.clone());
+        checks.push(match sync.count_orphans() {
+            Ok(0) => Check::ok("orphans", "no orphaned destination entries".to_string()),
+            Ok(count) => Check::problem(
+                "orphans",
+                Severity::Warn,
+                format!("{} destination entries have no source", count),
+                "Run `skillsync resync` with the daemon running; orphans go to the trash"
+                    .to_string(),
+            ),
+            Err(e) => Check::problem(
+                "orphans",
+                Severity::Warn,
+                format!("could not scan destinations: {:#}", e),
+                "Check the destination permissions above".to_string(),
+            ),
+        });
+    }
+
+    checks
+}
+
+/// Tools that configured destinations depend on, with versions
+fn check_tools(config: &Config, outcome: &preflight::PreflightOutcome, checks: &mut Vec<Check>) {
+    let mut needed: Vec<&'static preflight::ToolSpec> = Vec::new();
+    for dest in &config.destinations {
+        if let Some(spec) = preflight::destination_tool(&config.home, dest) {
+            if !needed.iter().any(|s| s.id == spec.id) {
+                needed.push(spec);
+            }
+        }
+    }
+
+    for spec in needed {
+        let Some(check) = outcome.tools.get(spec.id) else {
+            continue;
+        };
+        if spec.binaries.is_empty() {
+            continue;
+        }
+        checks.push(match (&check.binary, check.version) {
+            (Some(binary), version) => Check::ok(
+                spec.id,
+                format!(
+                    "{} {} at {}",
+                    spec.name,
+                    version.map_or("(version unknown)".to_string(), |v| v.to_string()),
+                    binary.display()
+                ),
+            ),
+            (None, _) => Check::problem(
+                spec.id,
+                Severity::Warn,
+                format!(
+                    "{} not found; its destinations are {}",
+                    spec.name,
+                    match config.tools.on_missing {
+                        MissingToolPolicy::Disable => "disabled",
+                        MissingToolPolicy::Warn => "synced anyway",
+                    }
+                ),
+                spec.install_hint.to_string(),
+            ),
+        });
+    }
+
+    let (_, warnings) = preflight::active_destinations(config, outcome);
+    for warning in warnings {
+        checks.push(Check::problem(
+            "versions",
+            Severity::Warn,
+            warning,
+            "Upgrade the tool, or expect the destination's format to be off".to_string(),
+        ));
+    }
+}
+
+/// A directory that must exist and be readable, and writable if `write`
+fn check_dir(name: &str, path: &Path, write: bool) -> Check {
+    if !path.exists() {
+        return Check::problem(
+            name,
+            Severity::Warn,
+            format!("{} does not exist", path.display()),
+            "Create it (skillsync doctor --fix)".to_string(),
+        )
+        .with_fix(Fix::CreateDir(path.to_path_buf()));
+    }
+    if !path.is_dir() {
+        return Check::problem(
+            name,
+            Severity::Fail,
+            format!("{} is not a directory", path.display()),
+            "Move the file out of the way".to_string(),
+        );
+    }
+    let accessible = fs::read_dir(path).is_ok() && (!write || writable(path));
+    if !accessible {
+        return Check::problem(
+            name,
+            Severity::Fail,
+            format!(
+                "{} is not {}",
+                path.display(),
+                if write { "writable" } else { "readable" }
+            ),
+            "Give your user read/write access (skillsync doctor --fix)".to_string(),
+        )
+        .with_fix(Fix::OwnerAccess(path.to_path_buf()));
+    }
+    Check::ok(name, format!("{} ok", path.display()))
+}
+
+fn writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(".skillsync-doctor-{}", std::process::id()));
+    let ok = fs::write(&probe, "").is_ok();
+    let _ = fs::remove_file(&probe);
+    ok
+}
+
+/// Lock, PID file and socket of the daemon; returns whether one is running
+fn check_daemon(config: &Config, checks: &mut Vec<Check>) -> bool {
+    let lock_file = config.lock_file();
+    let socket = config.control_socket();
+
+    match instance::holder(&lock_file) {
+        Ok(Some(pid)) => {
+            checks.push(Check::ok(
+                "daemon",
+                format!("running ({})", instance::describe_holder(pid)),
+            ));
+            true
+        }
+        Ok(None) => {
+            checks.push(Check::problem(
+                "daemon",
+                Severity::Warn,
+                "not running".to_string(),
+                "Start it with `launchctl start com.skillsync` or run `skillsync`".to_string(),
+            ));
+            let stale_pid = fs::read_to_string(&lock_file)
+                .ok()
+                .filter(|text| !text.trim().is_empty());
+            if let Some(pid) = stale_pid {
+                checks.push(
+                    Check::problem(
+                        "lock",
+                        Severity::Warn,
+                        format!("stale PID file names pid {}", pid.trim()),
+                        "Remove it (skillsync doctor --fix)".to_string(),
+                    )
+                    .with_fix(Fix::RemoveStale(lock_file)),
+                );
+            }
+            if socket.exists() {
+                checks.push(
+                    Check::problem(
+                        "socket",
+                        Severity::Warn,
+                        format!("stale control socket {}", socket.display()),
+                        "Remove it (skillsync doctor --fix)".to_string(),
+                    )
+                    .with_fix(Fix::RemoveStale(socket)),
+                );
+            }
+            false
+        }
+        Err(e) => {
+            checks.push(Check::problem(
+                "daemon",
+                Severity::Warn,
+                format!("could not inspect lock: {:#}", e),
+                "Check the state directory permissions".to_string(),
+            ));
+            false
+        }
+    }
+}
+
+fn check_service(home: &Path, running: bool, probes: &Probes) -> Check {
+    let Some(path) = service_file(home) else {
+        return Check::ok(
+            "service",
+            "no service manager support on this platform".to_string(),
+        );
+    };
+    if !path.exists() {
+        return Check::problem(
+            "service",
+            Severity::Warn,
+            format!("{} is missing", path.display()),
+            "Install it (skillsync doctor --fix) or run scripts/install.sh".to_string(),
+        )
+        .with_fix(Fix::InstallService(path));
+    }
+    let expected = std::env::current_exe().ok();
+    let program = fs::read_to_string(&path)
+        .ok()
+        .and_then(|definition| service_program(&definition));
+    if let (Some(expected), Some(program)) = (expected, program) {
+        // Compare resolved paths, the service may name a symlink to this binary
+        let resolved = fs::canonicalize(&program).ok();
+        if resolved != fs::canonicalize(&expected).ok() {
+            return Check::problem(
+                "service",
+                Severity::Warn,
+                format!(
+                    "{} runs {}, not this binary ({})",
+                    path.display(),
+                    program,
+                    expected.display()
+                ),
+                "Reinstall it (skillsync doctor --fix), then reload the service".to_string(),
+            )
+            .with_fix(Fix::InstallService(path));
+        }
+    }
+    match (probes.service_loaded)() {
+        Some(false) if !running => Check::problem(
+            "service",
+            Severity::Warn,
+            format!("{} is installed but not loaded", path.display()),
+            format!("Load it with: {}", service_load_hint(&path)),
+        ),
+        _ => Check::ok("service", format!("{} installed", path.display())),
+    }
+}
+
+const LAUNCHD_LABEL: &str = "com.skillsync";
+const PLIST_TEMPLATE: &str = include_str!("../resources/com.skillsync.plist");
+
+fn service_file(home: &Path) -> Option<PathBuf> {
+    if cfg!(target_os = "macos") {
+        Some(
+            home.join("Library/LaunchAgents")
+                .join(format!("{}.plist", LAUNCHD_LABEL)),
+        )
+    } else if cfg!(target_os = "linux") {
+        Some(home.join(".config/systemd/user/skillsync.service"))
+    } else {
+        None
+    }
+}
+
+fn service_loaded() -> Option<bool> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("launchctl");
+        command.args(["list", LAUNCHD_LABEL]);
+        command
+    } else {
+        let mut command = Command::new("systemctl");
+        command.args(["--user", "is-active", "--quiet", "skillsync"]);
+        command
+    };
+    command
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .ok()
+        .map(|status| status.success())
+}
+
+fn service_load_hint(path: &Path) -> String {
+    if cfg!(target_os = "macos") {
+        format!("launchctl load {}", path.display())
+    } else {
+        "systemctl --user daemon-reload && systemctl --user enable --now skillsync".to_string()
+    }
+}
+
+/// Service file running `exe` with `path_env` as PATH
+fn service_definition(exe: &Path, path_env: &str) -> String {
+    if cfg!(target_os = "macos") {
+        launchd_definition(exe, path_env)
+    } else {
+        systemd_definition(exe, path_env)
+    }
+}
+
+fn launchd_definition(exe: &Path, path_env: &str) -> String {
+    let env = format!(
+        "    <key>EnvironmentVariables</key>\n    <dict>\n        <key>PATH</key>\n        <string>{}</string>\n    </dict>\n",
+        xml_escape(path_env)
+    );
+    PLIST_TEMPLATE
+        .replace(
+            "/usr/local/bin/skillsync",
+            &xml_escape(&exe.display().to_string()),
+        )
+        .replace("</dict>\n</plist>", &format!("{}</dict>\n</plist>", env))
+}
+
+fn systemd_definition(exe: &Path, path_env: &str) -> String {
+    format!(
+        "[Unit]\nDescription=SkillSync skill mirroring daemon\n\n[Service]\nExecStart={}\nEnvironment={}\nRestart=always\n\n[Install]\nWantedBy=default.target\n",
+        systemd_quote(&exe.display().to_string()),
+        systemd_quote(&format!("PATH={}", path_env))
+    )
+}
+
+/// Program a service definition runs, as written by `service_definition`
+/// or `scripts/install.sh`
+fn service_program(definition: &str) -> Option<String> {
+    if let Some(line) = definition
+        .lines()
+        .find_map(|line| line.strip_prefix("ExecStart="))
+    {
+        let line = line.trim();
+        return Some(match line.strip_prefix('"') {
+            Some(quoted) => systemd_unquote(quoted.strip_suffix('"')?),
+            None => line.split_whitespace().next()?.to_string(),
+        });
+    }
+    let (_, after) = definition.split_once("<key>ProgramArguments</key>")?;
+    let (_, after) = after.split_once("<string>")?;
+    let (program, _) = after.split_once("</string>")?;
+    Some(xml_unescape(program.trim()))
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn xml_unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// A double-quoted systemd unit value; `%` would start a specifier
+fn systemd_quote(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('%', "%%")
+    )
+}
+
+fn systemd_unquote(value: &str) -> String {
+    let mut out = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' | '%' => out.extend(chars.next()),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_definitions_escape_values() {
+        let exe = Path::new("/Apps/A&B/skill sync");
+        let path_env = "/opt/<tools>:/bin";
+
+        let plist = launchd_definition(exe, path_env);
+        assert!(plist.contains("<string>/opt/&lt;tools&gt;:/bin</string>"));
+        assert!(plist.contains("<string>/Apps/A&amp;B/skill sync</string>"));
+        assert_eq!(
+            service_program(&plist).as_deref(),
+            Some("/Apps/A&B/skill sync")
+        );
+
+        let unit = systemd_definition(Path::new("/opt/100%\"x\"/skillsync"), "/bin");
+        assert!(unit.contains("ExecStart=\"/opt/100%%\\\"x\\\"/skillsync\"\n"));
+        assert_eq!(
+            service_program(&unit).as_deref(),
+            Some("/opt/100%\"x\"/skillsync")
+        );
+        assert_eq!(
+            service_program("[Service]\nExecStart=/usr/bin/skillsync run\n").as_deref(),
+            Some("/usr/bin/skillsync")
+        );
+    }
+}
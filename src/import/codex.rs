@@ -0,0 +1,38 @@
+//! Codex CLI custom prompts (`~/.codex/prompts/*.md`)
+//!
+//! Frontmatter is optional. When it is missing, the first line of the body is
+//! used as the description if it reads like a sentence rather than a heading.
+
+use std::path::Path;
+
+use super::ImportedSkill;
+use crate::transform::parse_frontmatter;
+
+pub(super) fn parse(name: String, path: &Path, content: &str) -> ImportedSkill {
+    let (frontmatter, body) = parse_frontmatter(content);
+
+    let description = frontmatter
+        .description
+        .filter(|d| !d.is_empty())
+        .or_else(|| {
+            body.lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .filter(|line| !line.starts_with('#'))
+                .map(str::to_string)
+        });
+
+    let metadata = frontmatter
+        .fields
+        .into_iter()
+        .filter(|(key, value)| key == "argument-hint" && !value.is_empty())
+        .collect();
+
+    ImportedSkill {
+        name,
+        description,
+        body,
+        metadata,
+        origin: path.to_path_buf(),
+    }
+}
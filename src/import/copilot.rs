@@ -0,0 +1,29 @@
+//! GitHub Copilot prompt files (`.prompt.md`)
+//!
+//! Prompt files may declare `description`, `mode`, `model` and `tools` in
+//! frontmatter. Only the description maps onto a Claude field.
+
+use std::path::Path;
+
+use super::ImportedSkill;
+use crate::transform::parse_frontmatter;
+
+pub(super) fn parse(name: String, path: &Path, content: &str) -> ImportedSkill {
+    let (frontmatter, body) = parse_frontmatter(content);
+
+    let metadata = frontmatter
+        .fields
+        .into_iter()
+        .filter(|(key, value)| {
+            matches!(key.as_str(), "mode" | "model" | "tools") && !value.is_empty()
+        })
+        .collect();
+
+    ImportedSkill {
+        name,
+        description: frontmatter.description.filter(|d| !d.is_empty()),
+        body,
+        metadata,
+        origin: path.to_path_buf(),
+    }
+}
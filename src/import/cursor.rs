@@ -0,0 +1,31 @@
+//! Cursor project rules (`.mdc`)
+//!
+//! Rules carry `description`, `globs` and `alwaysApply` in YAML frontmatter.
+//! The description becomes the skill description; the rest is kept as metadata.
+
+use std::path::Path;
+
+use super::ImportedSkill;
+use crate::transform::parse_frontmatter;
+
+pub(super) fn parse(name: String, path: &Path, content: &str) -> ImportedSkill {
+    let (frontmatter, body) = parse_frontmatter(content);
+
+    let metadata = frontmatter
+        .fields
+        .into_iter()
+        .filter(|(key, value)| matches!(key.as_str(), "globs" | "alwaysApply") && !value.is_empty())
+        .map(|(key, value)| match key.as_str() {
+            "alwaysApply" => ("always-apply".to_string(), value),
+            _ => (key, value),
+        })
+        .collect();
+
+    ImportedSkill {
+        name,
+        description: frontmatter.description.filter(|d| !d.is_empty()),
+        body,
+        metadata,
+        origin: path.to_path_buf(),
+    }
+}
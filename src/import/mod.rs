@@ -0,0 +1,291 @@
+//! Import skills from other tools' prompt formats into the Claude source
+//!
+//! Each supported format has its own parser that turns a foreign file into an
+//! [`ImportedSkill`]. Imported skills are written into the Claude source as
+//! `<name>/SKILL.md` directories, from where the daemon mirrors them as usual.
+
+mod codex;
+mod copilot;
+mod cursor;
+
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use crate::trash::Trash;
+
+/// Foreign prompt formats that can be imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// Cursor project rules (`.cursor/rules/*.mdc`)
+    CursorRules,
+    /// GitHub Copilot prompt files (`.github/prompts/*.prompt.md`)
+    CopilotPrompts,
+    /// Codex CLI custom prompts (`~/.codex/prompts/*.md`)
+    CodexPrompts,
+}
+
+impl ImportFormat {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "cursor" => Ok(Self::CursorRules),
+            "copilot" => Ok(Self::CopilotPrompts),
+            "codex" => Ok(Self::CodexPrompts),
+            other => bail!(
+                "Unknown import format '{}' (expected cursor, copilot or codex)",
+                other
+            ),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::CursorRules => "cursor",
+            Self::CopilotPrompts => "copilot",
+            Self::CodexPrompts => "codex",
+        }
+    }
+
+    /// Conventional location of this format's files
+    pub fn default_dir(&self, home: &Path, cwd: &Path) -> PathBuf {
+        match self {
+            Self::CursorRules => cwd.join(".cursor").join("rules"),
+            Self::CopilotPrompts => cwd.join(".github").join("prompts"),
+            Self::CodexPrompts => home.join(".codex").join("prompts"),
+        }
+    }
+
+    /// Skill name for a file of this format, or `None` if the file is not one
+    fn skill_stem(&self, path: &Path) -> Option<String> {
+        let file_name = path.file_name()?.to_str()?;
+        let stem = match self {
+            Self::CursorRules => file_name.strip_suffix(".mdc")?,
+            Self::CopilotPrompts => file_name.strip_suffix(".prompt.md")?,
+            Self::CodexPrompts => file_name.strip_suffix(".md")?,
+        };
+        Some(stem.to_string())
+    }
+
+    fn parse_file(&self, name: String, path: &Path, content: &str) -> ImportedSkill {
+        match self {
+            Self::CursorRules => cursor::parse(name, path, content),
+            Self::CopilotPrompts => copilot::parse(name, path, content),
+            Self::CodexPrompts => codex::parse(name, path, content),
+        }
+    }
+}
+
+/// A foreign prompt normalized into the pieces of a Claude skill
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedSkill {
+    pub name: String,
+    pub description: Option<String>,
+    pub body: String,
+    /// Format-specific settings preserved under `metadata:` in SKILL.md
+    pub metadata: Vec<(String, String)>,
+    pub origin: PathBuf,
+}
+
+impl ImportedSkill {
+    /// Render as a Claude SKILL.md document
+    pub fn to_skill_md(&self, format: ImportFormat) -> String {
+        let description = self
+            .description
+            .clone()
+            .unwrap_or_else(|| format!("Imported from {} prompt {}", format.label(), self.name));
+
+        let mut out = String::from("---\n");
+        out.push_str(&format!("name: {}\n", self.name));
+        out.push_str(&format!("description: {}\n", yaml_scalar(&description)));
+        out.push_str("metadata:\n");
+        out.push_str(&format!("  imported-from: {}\n", format.label()));
+        for (key, value) in &self.metadata {
+            out.push_str(&format!("  {}: {}\n", key, yaml_scalar(value)));
+        }
+        out.push_str("---\n\n");
+        out.push_str(self.body.trim_end());
+        out.push('\n');
+        out
+    }
+}
+
+/// What importing a single skill will do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportAction {
+    Create,
+    Overwrite,
+    /// A skill with this name already exists and `force` was not given
+    SkipExisting,
+    /// An earlier file in this import has the same skill name
+    SkipDuplicate,
+}
+
+#[derive(Debug)]
+pub struct PlannedImport {
+    pub skill: ImportedSkill,
+    pub target: PathBuf,
+    pub action: ImportAction,
+}
+
+/// Preview of an import, computed before anything is written
+#[derive(Debug)]
+pub struct ImportPlan {
+    pub format: ImportFormat,
+    pub entries: Vec<PlannedImport>,
+}
+
+/// Read every file of `format` in `dir` and normalize it into a skill
+pub fn discover(format: ImportFormat, dir: &Path) -> Result<Vec<ImportedSkill>> {
+    if !dir.is_dir() {
+        bail!("{} is not a directory", dir.display());
+    }
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+
+    let mut skills = Vec::new();
+    for path in paths {
+        let Some(stem) = format.skill_stem(&path) else {
+            continue;
+        };
+        let name = slugify(&stem);
+        if name.is_empty() {
+            continue;
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        skills.push(format.parse_file(name, &path, &content));
+    }
+
+    Ok(skills)
+}
+
+/// Decide what to do with each skill given the current Claude source
+pub fn plan(
+    format: ImportFormat,
+    skills: Vec<ImportedSkill>,
+    source: &Path,
+    force: bool,
+) -> ImportPlan {
+    let mut planned = BTreeSet::new();
+    let entries = skills
+        .into_iter()
+        .map(|skill| {
+            let target = source.join(&skill.name);
+            let action = if !planned.insert(skill.name.clone()) {
+                ImportAction::SkipDuplicate
+            } else if !target.exists() {
+                ImportAction::Create
+            } else if force {
+                ImportAction::Overwrite
+            } else {
+                ImportAction::SkipExisting
+            };
+            PlannedImport {
+                skill,
+                target,
+                action,
+            }
+        })
+        .collect();
+
+    ImportPlan { format, entries }
+}
+
+/// Write the planned skills, returning how many were written
+///
+/// A skill being overwritten is first moved to `trash` as a whole, so it can
+/// be restored; without a trash only its SKILL.md is replaced.
+pub fn apply(plan: &ImportPlan, trash: Option<&Trash>) -> Result<usize> {
+    let snapshot = trash.map(Trash::snapshot_id);
+    let mut written = 0;
+    for entry in &plan.entries {
+        if matches!(
+            entry.action,
+            ImportAction::SkipExisting | ImportAction::SkipDuplicate
+        ) {
+            continue;
+        }
+        if let (ImportAction::Overwrite, Some(trash), Some(snapshot)) =
+            (entry.action, trash, &snapshot)
+        {
+            trash.discard(&entry.target, snapshot).with_context(|| {
+                format!("Failed to move {} to the trash", entry.target.display())
+            })?;
+            info!(skill = %entry.skill.name, snapshot = %snapshot, "moved replaced skill to trash");
+        }
+        fs::create_dir_all(&entry.target)
+            .with_context(|| format!("Failed to create {}", entry.target.display()))?;
+        let skill_md = entry.target.join("SKILL.md");
+        fs::write(&skill_md, entry.skill.to_skill_md(plan.format))
+            .with_context(|| format!("Failed to write {}", skill_md.display()))?;
+        info!(skill = %entry.skill.name, from = %entry.skill.origin.display(), "imported skill");
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Lowercase, hyphen-separated skill name as Claude expects
+fn slugify(stem: &str) -> String {
+    let mut out = String::new();
+    for c in stem.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    out.trim_matches('-').to_string()
+}
+
+/// Quote a YAML scalar when it could otherwise be misread
+fn yaml_scalar(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value.contains(": ")
+        || value.contains(" #")
+        || value.starts_with(|c: char| "!&*{}[]|>'\"%@`,?:-#".contains(c))
+        || value != value.trim();
+    if needs_quotes {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("My Rule_v2"), "my-rule-v2");
+        assert_eq!(slugify("--review--"), "review");
+    }
+
+    #[test]
+    fn test_yaml_scalar_quotes_special_values() {
+        assert_eq!(yaml_scalar("plain text"), "plain text");
+        assert_eq!(yaml_scalar("*.rs"), "\"*.rs\"");
+        assert_eq!(yaml_scalar("a: b"), "\"a: b\"");
+    }
+
+    #[test]
+    fn test_skill_stem_per_format() {
+        let path = Path::new("review.prompt.md");
+        assert_eq!(
+            ImportFormat::CopilotPrompts.skill_stem(path),
+            Some("review".to_string())
+        );
+        assert_eq!(ImportFormat::CursorRules.skill_stem(path), None);
+        assert_eq!(
+            ImportFormat::CodexPrompts.skill_stem(path),
+            Some("review.prompt".to_string())
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! Inline files a skill references into its flattened Gemini TOML prompt
+//!
+//! A Gemini command is a single prompt, so `references/api.md` next to the
+//! SKILL.md is lost unless its text is copied in. `@include path` lines are
+//! replaced by the file in place; files reached through relative Markdown
+//! links are appended under "Referenced files". Only files inside the skill
+//! directory are read, each at most once. With link rewriting, links inside
+//! an inlined Markdown file are made absolute against that file's directory.
+
+use serde::Deserialize;
+use std::collections::{BTreeSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::rewrite::absolutize_links;
+use crate::transform::relative_links;
+
+/// `[destinations.inline]`: opt-in inlining for a `gemini-toml` destination
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InlineSettings {
+    /// Files larger than this are not inlined
+    pub max_file_bytes: u64,
+    /// Stop inlining once the prompt has grown by this much
+    pub max_total_bytes: u64,
+}
+
+impl Default for InlineSettings {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: 64 * 1024,
+            max_total_bytes: 256 * 1024,
+        }
+    }
+}
+
+struct Inliner<'a> {
+    settings: &'a InlineSettings,
+    /// Canonical skill directory; nothing outside it is read
+    root: PathBuf,
+    /// The skill directory as given, for rewritten links
+    skill_dir: PathBuf,
+    /// Make relative links in inlined Markdown absolute
+    rewrite_links: bool,
+    /// Bytes inlined so far
+    total: u64,
+    /// Files already inlined or queued, so each appears once
+    seen: BTreeSet<PathBuf>,
+}
+
+/// `body` of the SKILL.md in `skill_dir` with its references inlined
+///
+/// Files that cannot be inlined (outside the skill, too large, binary, an
+/// include cycle, or containing `'''`, which the TOML prompt cannot hold)
+/// are replaced by an HTML comment saying why. Links in `body` itself are
+/// left for the caller to rewrite.
+pub fn inline_references(
+    body: &str,
+    skill_dir: &Path,
+    settings: &InlineSettings,
+    rewrite_links: bool,
+) -> String {
+    let Ok(root) = skill_dir.canonicalize() else {
+        return body.to_string();
+    };
+    let skill_md = root.join("SKILL.md");
+    let mut inliner = Inliner {
+        settings,
+        root: root.clone(),
+        skill_dir: skill_dir.to_path_buf(),
+        rewrite_links,
+        total: 0,
+        seen: BTreeSet::from([skill_md.clone()]),
+    };
+
+    let mut links = Vec::new();
+    let mut output = inliner.expand(body, &skill_md, &mut vec![skill_md.clone()], &mut links);
+    let mut queue: VecDeque<(PathBuf, String)> = links.into();
+    let mut appended = Vec::new();
+
+    while let Some((base, target)) = queue.pop_front() {
+        let Some(path) = inliner.resolve(&base, &target) else {
+            continue;
+        };
+        if path.is_dir() || !inliner.seen.insert(path.clone()) {
+            continue;
+        }
+        let name = inliner.display(&path);
+        let section = match inliner.read(&path) {
+            Ok(text) => {
+                let mut links = Vec::new();
+                let text = inliner.expand(&text, &path, &mut vec![path.clone()], &mut links);
+                queue.extend(links);
+                fenced(&path, &inliner.relocate(&text, &path))
+            }
+            Err(reason) => note(&name, &reason),
+        };
+        appended.push(format!("## {}\n\n{}", name, section.trim_end()));
+    }
+
+    if !appended.is_empty() {
+        output = format!(
+            "{}\n\n---\n\n# Referenced files\n\n{}\n",
+            output.trim_end(),
+            appended.join("\n\n")
+        );
+    }
+    output
+}
+
+impl Inliner<'_> {
+    /// Replace `@include path` lines in `text` from `file`, recursively
+    ///
+    /// `stack` holds the files being expanded, to detect include cycles.
+    /// The relative links of every expanded file are added to `links` with
+    /// the directory they resolve against.
+    fn expand(
+        &mut self,
+        text: &str,
+        file: &Path,
+        stack: &mut Vec<PathBuf>,
+        links: &mut Vec<(PathBuf, String)>,
+    ) -> String {
+        let dir = file.parent().unwrap_or(&self.root).to_path_buf();
+        links.extend(
+            relative_links(text)
+                .into_iter()
+                .map(|target| (dir.clone(), target)),
+        );
+        let mut out = String::new();
+        for line in text.split_inclusive('\n') {
+            let Some(target) = include_target(line) else {
+                out.push_str(line);
+                continue;
+            };
+            let Some(path) = self.resolve(&dir, target) else {
+                out.push_str(&note(target, "outside the skill directory or missing"));
+                out.push('\n');
+                continue;
+            };
+            if stack.contains(&path) {
+                out.push_str(&note(target, "include cycle"));
+                out.push('\n');
+                continue;
+            }
+            match self.read(&path) {
+                Ok(included) => {
+                    self.seen.insert(path.clone());
+                    stack.push(path.clone());
+                    let expanded = self.expand(&included, &path, stack, links);
+                    stack.pop();
+                    out.push_str(&self.relocate(&expanded, &path));
+                    if !expanded.ends_with('\n') {
+                        out.push('\n');
+                    }
+                }
+                Err(reason) => {
+                    out.push_str(&note(target, &reason));
+                    out.push('\n');
+                }
+            }
+        }
+        out
+    }
+
+    /// `text` from the Markdown file `path` with its relative links made
+    /// absolute, when link rewriting is on
+    fn relocate(&self, text: &str, path: &Path) -> String {
+        let markdown = path.extension().is_some_and(|e| e == "md");
+        if !self.rewrite_links || !markdown {
+            return text.to_string();
+        }
+        let dir = path.parent().unwrap_or(&self.root);
+        let base = self
+            .skill_dir
+            .join(dir.strip_prefix(&self.root).unwrap_or(dir));
+        absolutize_links(text, &base.display().to_string())
+    }
+
+    /// Canonical path of `target` relative to `base`, if it is inside the skill
+    fn resolve(&self, base: &Path, target: &str) -> Option<PathBuf> {
+        let path = base.join(target).canonicalize().ok()?;
+        path.starts_with(&self.root).then_some(path)
+    }
+
+    /// File content, if it fits the size caps and the TOML prompt
+    fn read(&mut self, path: &Path) -> Result<String, String> {
+        let size = fs::metadata(path).map_err(|e| e.to_string())?.len();
+        if size > self.settings.max_file_bytes {
+            return Err(format!(
+                "{} bytes exceeds max_file_bytes ({})",
+                size, self.settings.max_file_bytes
+            ));
+        }
+        if self.total + size > self.settings.max_total_bytes {
+            return Err(format!(
+                "would exceed max_total_bytes ({})",
+                self.settings.max_total_bytes
+            ));
+        }
+        let text = fs::read_to_string(path).map_err(|_| "not a UTF-8 text file".to_string())?;
+        if text.contains("'''") {
+            return Err(
+                "contains a triple single quote, which the TOML prompt cannot hold".to_string(),
+            );
+        }
+        self.total += size;
+        Ok(text)
+    }
+
+    fn display(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+}
+
+/// Target of an `@include path` line
+fn include_target(line: &str) -> Option<&str> {
+    let target = line.trim().strip_prefix("@include")?;
+    let target = target.strip_prefix(char::is_whitespace)?.trim();
+    (!target.is_empty()).then_some(target)
+}
+
+/// Markdown inlined as-is; anything else in a code fence
+fn fenced(path: &Path, text: &str) -> String {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    if extension == "md" {
+        return text.to_string();
+    }
+    let fence = if text.contains("```") { "````" } else { "```" };
+    format!("{}{}\n{}\n{}", fence, extension, text.trim_end(), fence)
+}
+
+fn note(target: &str, reason: &str) -> String {
+    format!("<!-- skillsync: {} not inlined: {} -->", target, reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_include_target() {
+        assert_eq!(include_target("@include refs/a.md\n"), Some("refs/a.md"));
+        assert_eq!(include_target("  @include   b.txt"), Some("b.txt"));
+        assert_eq!(include_target("@included x"), None);
+        assert_eq!(include_target("see @include x"), None);
+    }
+}
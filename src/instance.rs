@@ -0,0 +1,96 @@
+//! Single-instance lock
+//!
+//! The daemon holds an advisory lock on `<state>/skillsync.pid` for its whole
+//! lifetime and writes its PID into the file. A second daemon (launchd's
+//! `KeepAlive` plus a manual run) refuses to start instead of racing the
+//! first one on the destinations, and one-shot commands that write
+//! destinations know to hand their work to the running daemon.
+
+use anyhow::{Context, Result};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Held lock; released when dropped or when the process exits
+#[derive(Debug)]
+pub struct InstanceLock {
+    file: File,
+}
+
+#[derive(Debug)]
+pub enum LockState {
+    Acquired(InstanceLock),
+    /// Another process holds the lock; its PID if the file names one
+    Held(Option<u32>),
+}
+
+impl InstanceLock {
+    pub fn acquire(path: &Path) -> Result<LockState> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        // Never truncate or remove the file before holding the lock, or two
+        // processes could end up locking different inodes
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let mut text = String::new();
+                let _ = file.read_to_string(&mut text);
+                return Ok(LockState::Held(text.trim().parse().ok()));
+            }
+            Err(TryLockError::Error(e)) => {
+                return Err(e).with_context(|| format!("Failed to lock {}", path.display()));
+            }
+        }
+
+        file.set_len(0)?;
+        writeln!(file, "{}", std::process::id())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(LockState::Acquired(InstanceLock { file }))
+    }
+}
+
+impl Drop for InstanceLock {
+    /// Empty the PID file while still holding the lock, so only a crashed
+    /// instance leaves a PID behind
+    fn drop(&mut self) {
+        let _ = self.file.set_len(0);
+    }
+}
+
+/// Who holds the lock at `path`, without taking it; `None` when nobody does
+pub fn holder(path: &Path) -> Result<Option<Option<u32>>> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to open {}", path.display())),
+    };
+    match file.try_lock_shared() {
+        Ok(()) => Ok(None),
+        Err(TryLockError::WouldBlock) => {
+            let mut text = String::new();
+            let _ = file.read_to_string(&mut text);
+            Ok(Some(text.trim().parse().ok()))
+        }
+        Err(TryLockError::Error(e)) => {
+            Err(e).with_context(|| format!("Failed to lock {}", path.display()))
+        }
+    }
+}
+
+/// "pid 123" or a placeholder when the lock file was empty
+pub fn describe_holder(pid: Option<u32>) -> String {
+    match pid {
+        Some(pid) => format!("pid {}", pid),
+        None => "unknown pid".to_string(),
+    }
+}
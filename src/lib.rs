@@ -3,18 +3,35 @@
 //! A macOS daemon that watches ~/.claude/skills/ and mirrors changes
 //! to multiple destinations with tool-specific transformations.
 
+pub mod cli;
+pub mod clock;
 pub mod config;
+pub mod control;
 pub mod destination;
+pub mod doctor;
+pub mod import;
+pub mod inline;
+pub mod instance;
+pub mod lint;
+pub mod manifest;
+pub mod persist;
 pub mod preflight;
+pub mod project;
+pub mod rewrite;
+pub mod safety;
+pub mod status;
 pub mod sync;
 pub mod transform;
+pub mod trash;
+pub mod version;
 pub mod watcher;
 
 pub use config::Config;
 pub use destination::{Destination, DestinationType};
+pub use status::StatusFile;
 pub use sync::SkillSync;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use std::fs;
 use tracing::Level;
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
@@ -54,27 +71,64 @@ pub fn run() -> Result<()> {
 
     tracing::info!("skillsync daemon starting");
 
-    // Preflight checks (non-fatal except missing Claude source)
-    let outcome = preflight::check_all(&config)?;
-    if !outcome.claude_ok {
-        // Without a source to watch, there's nothing to do. Exit gracefully.
+    // Two daemons would race each other on every destination
+    let _lock = match instance::InstanceLock::acquire(&config.lock_file())? {
+        instance::LockState::Acquired(lock) => lock,
+        instance::LockState::Held(pid) => {
+            let holder = instance::describe_holder(pid);
+            tracing::error!(holder = %holder, "another skillsync instance is running, exiting");
+            bail!(
+                "skillsync is already running ({}); use `skillsync status` or `skillsync shutdown`",
+                holder
+            );
+        }
+    };
+
+    // Preflight checks (non-fatal)
+    let outcome = preflight::check_all(&config);
+    if !outcome.present("claude") {
+        // The watcher picks the source up as soon as it is created
         tracing::warn!(
-            "Exiting: Claude skills directory missing. Create ~/.claude/skills and restart."
+            "Claude skills directory missing. Waiting for ~/.claude/skills to be created."
         );
-        return Ok(());
     }
-    if !outcome.gemini_cli_ok {
-        tracing::warn!("Exiting: Gemini CLI not found on PATH. Install and expose 'gemini' before running SkillSync.");
-        return Ok(());
+
+    // Destinations of missing tools are disabled or kept according to policy
+    let installed = |spec: &preflight::ToolSpec| outcome.installed(spec);
+    for (dest, spec) in preflight::missing_tools(&config, installed) {
+        match config.tools.on_missing {
+            config::MissingToolPolicy::Disable => tracing::warn!(
+                path = %dest.base_path.display(),
+                tool = spec.name,
+                "destination disabled until the tool is installed"
+            ),
+            config::MissingToolPolicy::Warn => tracing::warn!(
+                path = %dest.base_path.display(),
+                tool = spec.name,
+                "tool not installed, syncing destination anyway"
+            ),
+        }
     }
-    if !outcome.codex_cli_ok {
-        tracing::warn!(
-            "Codex CLI not found on PATH. Skills will still be synced to ~/.codex/skills/."
-        );
+
+    let (destinations, warnings) = preflight::active_destinations(&config, &outcome);
+    for warning in &warnings {
+        tracing::warn!("{}", warning);
     }
 
     // Initialize sync manager
-    let sync = SkillSync::new(config.source.clone(), config.destinations);
+    let mut sync = SkillSync::new(config.source.clone(), destinations)
+        .with_sources(config.sources.clone())
+        .with_home(config.home.clone());
+    if config.trash.enabled {
+        sync = sync.with_trash(config.trash_store());
+    }
+    sync = sync
+        .with_deletion_guard(config.deletion_guard())
+        .with_status(StatusFile::new(config.status_file()));
+    if let Some(status) = sync.status() {
+        let tools = outcome.tool_status();
+        status.update(|s| s.tools = tools);
+    }
     sync.ensure_directories()?;
 
     // Perform initial sync
@@ -83,7 +137,7 @@ pub fn run() -> Result<()> {
     }
 
     // Start watching and syncing
-    watcher::watch_and_sync(sync, &config.source)?;
+    watcher::watch_and_sync(sync, &config)?;
 
     Ok(())
 }
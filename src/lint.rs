@@ -0,0 +1,330 @@
+//! `skillsync lint`: validate skills before they are mirrored
+//!
+//! Rules cover Claude's SKILL.md conventions (frontmatter, name, description,
+//! size), what each destination format can represent, and relative links to
+//! files inside the skill.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::destination::{Destination, DestinationType};
+use crate::preflight;
+use crate::sync::{render_toml, template_context};
+use crate::transform::{apply_template, parse_frontmatter, relative_links};
+
+/// Longest `name` Claude accepts
+const MAX_NAME_LEN: usize = 64;
+/// Longest `description` Claude accepts
+const MAX_DESCRIPTION_LEN: usize = 1024;
+/// Claude recommends keeping SKILL.md under this many lines
+const MAX_BODY_LINES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub skill: String,
+    pub rule: &'static str,
+    pub level: Level,
+    pub message: String,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct LintReport {
+    pub skills: usize,
+    pub findings: Vec<Finding>,
+}
+
+impl LintReport {
+    pub fn count(&self, level: Level) -> usize {
+        self.findings.iter().filter(|f| f.level == level).count()
+    }
+}
+
+/// Lint every skill directory under `source` for the given destinations
+///
+/// Templates see the current user's home directory.
+pub fn lint_source(source: &Path, destinations: &[Destination]) -> Result<LintReport> {
+    let home = home::home_dir().unwrap_or_default();
+    lint_sources(&[source.to_path_buf()], destinations, &home)
+}
+
+/// Lint the tree merged from `sources`, highest precedence first
+///
+/// Like the sync, only the winning copy of each name is checked; copies a
+/// higher source hides never reach a destination. A missing source is a
+/// warning, as the sync waits for it to appear.
+pub fn lint_sources(
+    sources: &[PathBuf],
+    destinations: &[Destination],
+    home: &Path,
+) -> Result<LintReport> {
+    let mut seen = BTreeSet::new();
+    let mut report = LintReport::default();
+    for source in sources {
+        if !source.exists() {
+            report.findings.push(Finding {
+                skill: String::new(),
+                rule: "source-missing",
+                level: Level::Warning,
+                message: "source directory does not exist; it is synced once it appears"
+                    .to_string(),
+                path: source.clone(),
+            });
+            continue;
+        }
+        let mut entries: Vec<PathBuf> = fs::read_dir(source)
+            .with_context(|| format!("Failed to read {}", source.display()))?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| !is_hidden(path))
+            .filter(|path| seen.insert(file_name(path)))
+            .collect();
+        entries.sort();
+
+        for path in entries {
+            let name = file_name(&path);
+            if path.is_dir() {
+                report.skills += 1;
+                report
+                    .findings
+                    .extend(lint_skill(&path, destinations, home));
+            } else {
+                report.findings.push(Finding {
+                    skill: name,
+                    rule: "stray-file",
+                    level: Level::Warning,
+                    message: "only directories are skills; this file is copied as-is".to_string(),
+                    path,
+                });
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Lint one skill directory
+///
+/// Templates are rendered for each destination as the sync renders them,
+/// with `home` as the home directory, and the TOML check runs on the result.
+pub fn lint_skill(dir: &Path, destinations: &[Destination], home: &Path) -> Vec<Finding> {
+    let skill = file_name(dir);
+    let skill_md = dir.join("SKILL.md");
+    let mut findings = Vec::new();
+    let mut add = |rule: &'static str, level: Level, message: String, path: &Path| {
+        findings.push(Finding {
+            skill: skill.clone(),
+            rule,
+            level,
+            message,
+            path: path.to_path_buf(),
+        });
+    };
+
+    let content = match fs::read_to_string(&skill_md) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            add(
+                "skill-md-missing",
+                Level::Error,
+                "no SKILL.md; the skill is copied but never loaded".to_string(),
+                &skill_md,
+            );
+            return findings;
+        }
+        Err(e) => {
+            add(
+                "unreadable",
+                Level::Error,
+                format!("cannot read SKILL.md as UTF-8: {}", e),
+                &skill_md,
+            );
+            return findings;
+        }
+    };
+
+    let (frontmatter, body) = parse_frontmatter(&content);
+    if body == content {
+        add(
+            "frontmatter-missing",
+            Level::Error,
+            "SKILL.md must start with a `---` frontmatter block".to_string(),
+            &skill_md,
+        );
+    } else {
+        let field = |key: &str| {
+            frontmatter
+                .fields
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+        };
+
+        match field("name").filter(|name| !name.is_empty()) {
+            None => add(
+                "name-missing",
+                Level::Error,
+                "frontmatter has no `name`".to_string(),
+                &skill_md,
+            ),
+            Some(name) => {
+                if name != skill {
+                    add(
+                        "name-mismatch",
+                        Level::Error,
+                        format!("name '{}' does not match directory '{}'", name, skill),
+                        &skill_md,
+                    );
+                }
+                let well_formed = name.len() <= MAX_NAME_LEN
+                    && name
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+                if !well_formed {
+                    add(
+                        "name-format",
+                        Level::Warning,
+                        format!(
+                            "name should be lowercase letters, digits and hyphens, at most {} characters",
+                            MAX_NAME_LEN
+                        ),
+                        &skill_md,
+                    );
+                }
+                if name.contains("anthropic") || name.contains("claude") {
+                    add(
+                        "name-reserved",
+                        Level::Error,
+                        "name must not contain the reserved words 'anthropic' or 'claude'"
+                            .to_string(),
+                        &skill_md,
+                    );
+                }
+            }
+        }
+
+        match frontmatter.description.as_deref().filter(|d| !d.is_empty()) {
+            None => add(
+                "description-missing",
+                Level::Error,
+                "frontmatter has no `description`; agents use it to decide when to load the skill"
+                    .to_string(),
+                &skill_md,
+            ),
+            Some(description) if description.chars().count() > MAX_DESCRIPTION_LEN => add(
+                "description-too-long",
+                Level::Warning,
+                format!("description exceeds {} characters", MAX_DESCRIPTION_LEN),
+                &skill_md,
+            ),
+            Some(_) => {}
+        }
+
+        for tool in frontmatter.targets.names() {
+            if preflight::tool(tool).is_none() {
+                add(
+                    "target-unknown",
+                    Level::Warning,
+                    format!("`skillsync:` names unknown tool '{}'", tool),
+                    &skill_md,
+                );
+            }
+        }
+    }
+
+    let lines = body.lines().count();
+    if lines > MAX_BODY_LINES {
+        add(
+            "body-too-long",
+            Level::Warning,
+            format!(
+                "body has {} lines; move detail into referenced files (limit {})",
+                lines, MAX_BODY_LINES
+            ),
+            &skill_md,
+        );
+    }
+
+    let mut toml_errors = BTreeSet::new();
+    let mut template_errors = BTreeSet::new();
+    for dest in destinations {
+        let rendered = match apply_template(&content, &template_context(home, dest, &skill)) {
+            Ok(rendered) => rendered,
+            Err(e) => {
+                template_errors.insert(e.to_string());
+                continue;
+            }
+        };
+        if dest.dest_type != DestinationType::GeminiToml {
+            continue;
+        }
+        if let Err(e) = toml::from_str::<toml::Value>(&render_toml(&rendered, dest, dir)) {
+            toml_errors.insert(e.message().trim().to_string());
+        }
+    }
+    for error in toml_errors {
+        add(
+            "toml-invalid",
+            Level::Error,
+            format!("Gemini TOML output does not parse: {}", error),
+            &skill_md,
+        );
+    }
+    for error in template_errors {
+        add("template-invalid", Level::Error, error, &skill_md);
+    }
+
+    for file in markdown_files(dir) {
+        let Ok(text) = fs::read_to_string(&file) else {
+            continue;
+        };
+        let base = file.parent().unwrap_or(dir);
+        for target in relative_links(&text) {
+            if !base.join(&target).exists() {
+                add(
+                    "broken-link",
+                    Level::Error,
+                    format!("link target '{}' does not exist", target),
+                    &file,
+                );
+            }
+        }
+    }
+
+    findings
+}
+
+fn markdown_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return files;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            files.extend(markdown_files(&path));
+        } else if path.extension().is_some_and(|ext| ext == "md") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    files
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn is_hidden(path: &Path) -> bool {
+    file_name(path).starts_with('.')
+}
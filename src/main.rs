@@ -3,5 +3,5 @@
 use anyhow::Result;
 
 fn main() -> Result<()> {
-    skillsync::run()
+    skillsync::cli::main()
 }
@@ -0,0 +1,82 @@
+//! Record of the skills skillsync wrote into each destination
+//!
+//! Destinations inside a repository also hold files skillsync did not
+//! create, such as hand-written Gemini commands. A `SkillSync` with a
+//! manifest only treats entries listed in it as orphans, so those files are
+//! never removed.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::warn;
+
+use crate::persist::write_json_atomic;
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Entries {
+    /// Skill names per destination base path
+    destinations: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// Owned skills per destination, persisted as JSON
+pub struct Manifest {
+    path: PathBuf,
+    entries: Mutex<Entries>,
+}
+
+impl Manifest {
+    /// Read the manifest at `path`; a missing or unreadable file starts empty
+    pub fn load(path: PathBuf) -> Self {
+        let entries = match fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                warn!(path = %path.display(), error = %e, "ignoring invalid manifest");
+                Entries::default()
+            }),
+            Err(_) => Entries::default(),
+        };
+        Self {
+            path,
+            entries: Mutex::new(entries),
+        }
+    }
+
+    /// Whether skillsync wrote `skill` into the destination at `dest`
+    pub fn owns(&self, dest: &Path, skill: &str) -> bool {
+        self.skills(dest).contains(skill)
+    }
+
+    pub fn skills(&self, dest: &Path) -> BTreeSet<String> {
+        self.entries
+            .lock()
+            .unwrap()
+            .destinations
+            .get(&dest.display().to_string())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Replace the skills recorded for `dest`, writing the file if that changed
+    ///
+    /// Failing to write is logged, never fatal.
+    pub fn set(&self, dest: &Path, skills: BTreeSet<String>) {
+        let mut entries = self.entries.lock().unwrap();
+        let key = dest.display().to_string();
+        let unchanged = match entries.destinations.get(&key) {
+            Some(current) => *current == skills,
+            None => skills.is_empty(),
+        };
+        if unchanged {
+            return;
+        }
+        if skills.is_empty() {
+            entries.destinations.remove(&key);
+        } else {
+            entries.destinations.insert(key, skills);
+        }
+        if let Err(e) = write_json_atomic(&self.path, &*entries) {
+            warn!(path = %self.path.display(), error = %e, "failed to write manifest");
+        }
+    }
+}
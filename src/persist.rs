@@ -0,0 +1,18 @@
+//! JSON state files written atomically, shared by status and manifests
+
+use anyhow::Result;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// Write `value` as JSON via a temporary file, so readers never see a
+/// partial file and a crash never leaves one behind
+pub(crate) fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_string_pretty(value)?)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
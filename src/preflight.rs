@@ -1,248 +1,473 @@
 //! Preflight checks to validate environment before syncing
+//!
+//! Every supported tool is described once in [`TOOLS`]; the checks run
+//! generically over that registry.
 
-use anyhow::Result;
-use std::path::Path;
-use tracing::{error, info, warn};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{info, warn};
 
-use crate::config::Config;
+use crate::config::{Config, MissingToolPolicy};
+use crate::destination::Destination;
+use crate::status::ToolStatus;
+use crate::version::Version;
+
+/// How long `<tool> --version` may take before it is given up on
+const VERSION_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Somewhere a tool's binary may be installed besides PATH
+#[derive(Debug)]
+pub enum InstallLocation {
+    /// Directory holding the binary directly, `~/` relative to home
+    BinDir(&'static str),
+    /// Version manager root whose children each have a `bin/` directory
+    VersionManager(&'static str),
+}
+
+/// Where npm-installed CLIs end up on macOS
+const NODE_LOCATIONS: &[InstallLocation] = &[
+    InstallLocation::VersionManager("~/.nvm/versions/node"),
+    InstallLocation::VersionManager("~/.fnm/node-versions"),
+    InstallLocation::BinDir("~/.volta/bin"),
+    InstallLocation::VersionManager("~/.nodenv/versions"),
+    InstallLocation::VersionManager("~/.asdf/installs/nodejs"),
+    InstallLocation::BinDir("~/.npm-global/bin"),
+    // Homebrew (Apple Silicon)
+    InstallLocation::BinDir("/opt/homebrew/bin"),
+    // Homebrew (Intel)
+    InstallLocation::BinDir("/usr/local/bin"),
+];
+
+/// A tool skillsync reads from or writes for
+#[derive(Debug)]
+pub struct ToolSpec {
+    pub id: &'static str,
+    pub name: &'static str,
+    /// Binaries that prove the tool is installed; empty when only
+    /// `skills_dirs` can tell
+    pub binaries: &'static [&'static str],
+    pub locations: &'static [InstallLocation],
+    /// Arguments that make the binary print its version
+    pub version_args: &'static [&'static str],
+    /// Skill directories the tool reads, relative to home
+    pub skills_dirs: &'static [&'static str],
+    /// Logged when the tool is missing
+    pub install_hint: &'static str,
+}
+
+pub const TOOLS: &[ToolSpec] = &[
+    ToolSpec {
+        id: "claude",
+        name: "Claude Code",
+        binaries: &[],
+        locations: &[],
+        version_args: &[],
+        skills_dirs: &[".claude/skills"],
+        install_hint: "Install Claude Code or create ~/.claude/skills before running SkillSync.",
+    },
+    ToolSpec {
+        id: "gemini",
+        name: "Gemini CLI",
+        binaries: &["gemini"],
+        locations: NODE_LOCATIONS,
+        version_args: &["--version"],
+        skills_dirs: &[".gemini/skills", ".gemini/commands"],
+        install_hint: "Gemini destinations need the 'gemini' binary.\nRemediation: install the Gemini CLI:\n  • npm install -g @google/gemini-cli\nSupported Node.js installation methods:\n  • Homebrew (brew install node)\n  • nvm (Node Version Manager)\n  • fnm (Fast Node Manager)\n  • Volta (JavaScript toolchain manager)\n  • nodenv (rbenv-style version manager)\n  • asdf (multi-language version manager)\n  • Official installer from nodejs.org\nThe installer will automatically detect and configure your Node.js installation.",
+    },
+    ToolSpec {
+        id: "antigravity",
+        name: "Antigravity",
+        binaries: &[],
+        locations: &[],
+        version_args: &[],
+        skills_dirs: &[".gemini/antigravity/skills"],
+        install_hint: "The directory will be created; check that Antigravity is installed.",
+    },
+    ToolSpec {
+        id: "codex",
+        name: "Codex CLI",
+        binaries: &["codex"],
+        locations: NODE_LOCATIONS,
+        version_args: &["--version"],
+        skills_dirs: &[".codex/skills"],
+        install_hint: "Install with: npm install -g @openai/codex",
+    },
+    ToolSpec {
+        id: "cursor",
+        name: "Cursor",
+        binaries: &[],
+        locations: &[],
+        version_args: &[],
+        skills_dirs: &[".cursor/skills"],
+        install_hint: "The directory will be created; check that Cursor is installed.",
+    },
+];
+
+pub fn tool(id: &str) -> Option<&'static ToolSpec> {
+    TOOLS.iter().find(|spec| spec.id == id)
+}
+
+/// The tool that reads `dest`, matched on its skills directories
+pub fn tool_for(home: &Path, dest: &Destination) -> Option<&'static ToolSpec> {
+    TOOLS.iter().find(|spec| {
+        spec.skills_dirs
+            .iter()
+            .any(|dir| home.join(dir) == dest.base_path)
+    })
+}
+
+/// The tool `dest` is for: its declared tool, else the one owning its path
+pub fn destination_tool(home: &Path, dest: &Destination) -> Option<&'static ToolSpec> {
+    match &dest.tool {
+        Some(id) => tool(id),
+        None => tool_for(home, dest),
+    }
+}
+
+/// Configured destinations whose tool is not installed, with that tool
+pub fn missing_tools(
+    config: &Config,
+    installed: impl Fn(&ToolSpec) -> bool,
+) -> Vec<(&Destination, &'static ToolSpec)> {
+    config
+        .destinations
+        .iter()
+        .filter_map(|dest| {
+            let spec = destination_tool(&config.home, dest)?;
+            (!installed(spec)).then_some((dest, spec))
+        })
+        .collect()
+}
+
+/// Destinations to sync, leaving out those of missing tools under
+/// [`MissingToolPolicy::Disable`]
+pub fn enabled_destinations(
+    config: &Config,
+    installed: impl Fn(&ToolSpec) -> bool,
+) -> Vec<Destination> {
+    without_missing_tools(config, &config.destinations, installed)
+}
+
+/// Project destinations (relative to each project) to sync, under the same
+/// policy as [`enabled_destinations`]
+pub fn enabled_project_destinations(
+    config: &Config,
+    installed: impl Fn(&ToolSpec) -> bool,
+) -> Vec<Destination> {
+    without_missing_tools(config, &config.projects.destinations, installed)
+}
+
+fn without_missing_tools(
+    config: &Config,
+    destinations: &[Destination],
+    installed: impl Fn(&ToolSpec) -> bool,
+) -> Vec<Destination> {
+    if config.tools.on_missing == MissingToolPolicy::Warn {
+        return destinations.to_vec();
+    }
+    destinations
+        .iter()
+        .filter(|dest| destination_tool(&config.home, dest).is_none_or(&installed))
+        .cloned()
+        .collect()
+}
+
+/// Enabled destinations, plus a warning per destination whose tool version
+/// is outside what its renderer supports
+pub fn active_destinations(
+    config: &Config,
+    outcome: &PreflightOutcome,
+) -> (Vec<Destination>, Vec<String>) {
+    let enabled = enabled_destinations(config, |spec| outcome.installed(spec));
+    let warnings = version_warnings(&config.home, &enabled, |spec| outcome.version(spec));
+    (enabled, warnings)
+}
+
+/// Check each destination's renderer against the detected tool version
+///
+/// Destinations without a declared range or a known version pass silently;
+/// the others are still synced.
+pub fn version_warnings(
+    home: &Path,
+    destinations: &[Destination],
+    version_of: impl Fn(&ToolSpec) -> Option<Version>,
+) -> Vec<String> {
+    destinations
+        .iter()
+        .filter_map(|dest| {
+            let spec = destination_tool(home, dest)?;
+            let version = version_of(spec)?;
+            let support = dest.dest_type.support(spec.id)?;
+            (!support.range.contains(&version)).then(|| {
+                format!(
+                    "{} {} is outside {} supported by {:?} output for {}; syncing anyway",
+                    spec.name,
+                    version,
+                    support.range,
+                    dest.dest_type,
+                    dest.base_path.display()
+                )
+            })
+        })
+        .collect()
+}
+
+/// What preflight found for one tool
+#[derive(Debug)]
+pub struct ToolCheck {
+    pub spec: &'static ToolSpec,
+    /// Located binary, if the tool has any
+    pub binary: Option<PathBuf>,
+    /// Output of `--version`, when it could be parsed
+    pub version: Option<Version>,
+    /// Skills directories with whether each exists
+    pub skills_dirs: Vec<(PathBuf, bool)>,
+}
+
+impl ToolCheck {
+    /// Installed binary, or an existing skills directory for tools without one
+    pub fn present(&self) -> bool {
+        if self.spec.binaries.is_empty() {
+            self.skills_dirs.first().is_some_and(|(_, exists)| *exists)
+        } else {
+            self.binary.is_some()
+        }
+    }
+}
 
 pub struct PreflightOutcome {
-    pub claude_ok: bool,
-    pub gemini_cli_ok: bool,
-    pub antigravity_ok: bool,
-    pub codex_cli_ok: bool,
-    pub cursor_ok: bool,
+    /// Keyed by tool id
+    pub tools: BTreeMap<&'static str, ToolCheck>,
 }
 
 impl PreflightOutcome {
+    pub fn present(&self, id: &str) -> bool {
+        self.tools.get(id).is_some_and(ToolCheck::present)
+    }
+
     pub fn all_good(&self) -> bool {
-        self.claude_ok
-            && self.gemini_cli_ok
-            && self.antigravity_ok
-            && self.codex_cli_ok
-            && self.cursor_ok
-    }
-}
-
-pub fn check_all(cfg: &Config) -> Result<PreflightOutcome> {
-    let mut out = PreflightOutcome {
-        claude_ok: false,
-        gemini_cli_ok: false,
-        antigravity_ok: false,
-        codex_cli_ok: false,
-        cursor_ok: false,
-    };
-
-    // 1) Claude Code skills source must exist
-    if cfg.source.exists() {
-        out.claude_ok = true;
-        info!(path = %cfg.source.display(), "Claude skills source detected");
-    } else {
-        warn!(path = %cfg.source.display(), "Claude skills directory not found");
-        error!(
-            "Claude Code appears missing. Install or create ~/.claude/skills before running SkillSync."
-        );
+        self.tools.values().all(ToolCheck::present)
     }
 
-    // 2) Gemini CLI presence (best-effort)
-    // Check PATH first, then common npm/nvm installation locations
-    let gemini_found = if let Ok(path) = which::which("gemini") {
-        out.gemini_cli_ok = true;
-        info!(binary = %path.display(), "Gemini CLI detected");
-        true
-    } else {
-        // Check common npm/node installation locations:
-        // - nvm (Node Version Manager)
-        // - fnm (Fast Node Manager)
-        // - Volta (JavaScript toolchain manager)
-        // - nodenv (rbenv-style version manager)
-        // - asdf (multi-language version manager)
-        // - npm global installs
-        // - Homebrew (Apple Silicon and Intel)
-        // - system paths
-        let home = std::env::var("HOME").unwrap_or_default();
-        let search_paths = vec![
-            // nvm
-            format!("{}/.nvm/versions/node", home),
-            // fnm
-            format!("{}/.fnm/node-versions", home),
-            // Volta
-            format!("{}/.volta/bin/gemini", home),
-            // nodenv
-            format!("{}/.nodenv/versions", home),
-            // asdf
-            format!("{}/.asdf/installs/nodejs", home),
-            // npm global
-            format!("{}/.npm-global/bin/gemini", home),
-            // Homebrew (Apple Silicon - M1/M2/M3)
-            "/opt/homebrew/bin/gemini".to_string(),
-            // Homebrew (Intel Mac)
-            "/usr/local/bin/gemini".to_string(),
-        ];
-
-        let mut found = false;
-        for base_path in search_paths {
-            let path = Path::new(&base_path);
-            // For nvm/fnm/nodenv/asdf, we need to search recursively for the gemini binary
-            if base_path.contains("nvm")
-                || base_path.contains("fnm")
-                || base_path.contains("nodenv")
-                || base_path.contains("asdf")
-            {
-                if path.exists() {
-                    if let Ok(entries) = std::fs::read_dir(path) {
-                        for entry in entries.flatten() {
-                            let gemini_bin = entry.path().join("bin/gemini");
-                            if gemini_bin.exists() {
-                                out.gemini_cli_ok = true;
-                                info!(binary = %gemini_bin.display(), "Gemini CLI detected (version manager)");
-                                found = true;
-                                break;
-                            }
-                        }
-                    }
-                }
-            } else if path.exists() {
-                out.gemini_cli_ok = true;
-                info!(binary = %path.display(), "Gemini CLI detected");
-                found = true;
-                break;
-            }
-            if found {
-                break;
-            }
+    /// Whether the tool's binary was found; tools without one count as installed
+    pub fn installed(&self, spec: &ToolSpec) -> bool {
+        spec.binaries.is_empty() || self.tools.get(spec.id).is_some_and(|c| c.binary.is_some())
+    }
+
+    pub fn version(&self, spec: &ToolSpec) -> Option<Version> {
+        self.tools.get(spec.id).and_then(|check| check.version)
+    }
+
+    /// Per-tool summary for the status file
+    pub fn tool_status(&self) -> BTreeMap<String, ToolStatus> {
+        self.tools
+            .iter()
+            .map(|(id, check)| {
+                let status = ToolStatus {
+                    installed: check.present(),
+                    binary: check.binary.as_ref().map(|b| b.display().to_string()),
+                    version: check.version.map(|v| v.to_string()),
+                };
+                (id.to_string(), status)
+            })
+            .collect()
+    }
+}
+
+/// Look at every tool, logging what was found; missing tools are reported
+/// in the outcome, never as an error
+pub fn check_all(cfg: &Config) -> PreflightOutcome {
+    let mut versions = VersionProbe::default();
+    let tools = TOOLS
+        .iter()
+        .map(|spec| (spec.id, check_tool(spec, &cfg.home, &mut versions)))
+        .collect();
+    PreflightOutcome { tools }
+}
+
+/// Look at every tool again without logging, e.g. for the daemon's re-check
+pub fn probe_all(home: &Path, versions: &mut VersionProbe) -> PreflightOutcome {
+    let tools = TOOLS
+        .iter()
+        .map(|spec| (spec.id, probe_tool(spec, home, versions)))
+        .collect();
+    PreflightOutcome { tools }
+}
+
+fn probe_tool(spec: &'static ToolSpec, home: &Path, versions: &mut VersionProbe) -> ToolCheck {
+    let binary = locate(spec, home);
+    let version = binary
+        .as_deref()
+        .and_then(|binary| versions.version(binary, spec.version_args));
+    let skills_dirs = spec
+        .skills_dirs
+        .iter()
+        .map(|dir| {
+            let path = home.join(dir);
+            let exists = path.exists();
+            (path, exists)
+        })
+        .collect();
+    ToolCheck {
+        spec,
+        binary,
+        version,
+        skills_dirs,
+    }
+}
+
+/// Locate one tool and log what was found
+pub fn check_tool(spec: &'static ToolSpec, home: &Path, versions: &mut VersionProbe) -> ToolCheck {
+    let check = probe_tool(spec, home, versions);
+    if let Some(path) = &check.binary {
+        let version = check.version.map(|v| v.to_string());
+        info!(tool = spec.name, binary = %path.display(), version = version.as_deref().unwrap_or("unknown"), "tool detected");
+    }
+
+    for (path, exists) in &check.skills_dirs {
+        if *exists {
+            info!(tool = spec.name, path = %path.display(), "skills directory detected");
+        } else {
+            warn!(tool = spec.name, path = %path.display(), "skills directory not found");
         }
+    }
+
+    if !check.present() {
+        warn!("{} not found. {}", spec.name, spec.install_hint);
+    }
+    check
+}
 
-        if !found {
-            error!(
-                "Gemini CLI not found on PATH or in common installation locations.\nSkillSync requires the 'gemini' binary.\nRemediation: install the Gemini CLI:\n  • npm install -g @google/gemini-cli\nSupported Node.js installation methods:\n  • Homebrew (brew install node)\n  • nvm (Node Version Manager)\n  • fnm (Fast Node Manager)\n  • Volta (JavaScript toolchain manager)\n  • nodenv (rbenv-style version manager)\n  • asdf (multi-language version manager)\n  • Official installer from nodejs.org\nThe installer will automatically detect and configure your Node.js installation."
-            );
+/// Find the first of the tool's binaries on PATH or in its install locations
+pub fn locate(spec: &ToolSpec, home: &Path) -> Option<PathBuf> {
+    for binary in spec.binaries {
+        if let Ok(path) = which::which(binary) {
+            return Some(path);
         }
-        found
-    };
-
-    if !gemini_found {
-        out.gemini_cli_ok = false;
-    }
-
-    // 3) Codex CLI presence
-    // Check PATH first, then common npm/nvm installation locations
-    let codex_found = if let Ok(path) = which::which("codex") {
-        out.codex_cli_ok = true;
-        info!(binary = %path.display(), "Codex CLI detected");
-        true
-    } else {
-        // Reuse search paths from Gemini (assuming similar installation via npm/node)
-        let home = std::env::var("HOME").unwrap_or_default();
-        let search_paths = vec![
-            format!("{}/.nvm/versions/node", home),
-            format!("{}/.fnm/node-versions", home),
-            format!("{}/.volta/bin/codex", home),
-            format!("{}/.nodenv/versions", home),
-            format!("{}/.asdf/installs/nodejs", home),
-            format!("{}/.npm-global/bin/codex", home),
-            "/opt/homebrew/bin/codex".to_string(),
-            "/usr/local/bin/codex".to_string(),
-        ];
-
-        let mut found = false;
-        for base_path in search_paths {
-            let path = Path::new(&base_path);
-            if base_path.contains("nvm")
-                || base_path.contains("fnm")
-                || base_path.contains("nodenv")
-                || base_path.contains("asdf")
-            {
-                if path.exists() {
-                    if let Ok(entries) = std::fs::read_dir(path) {
-                        for entry in entries.flatten() {
-                            let codex_bin = entry.path().join("bin/codex");
-                            if codex_bin.exists() {
-                                out.codex_cli_ok = true;
-                                info!(binary = %codex_bin.display(), "Codex CLI detected (version manager)");
-                                found = true;
-                                break;
-                            }
-                        }
-                    }
-                }
-            } else if path.exists() {
-                out.codex_cli_ok = true;
-                info!(binary = %path.display(), "Codex CLI detected");
-                found = true;
-                break;
-            }
-            if found {
-                break;
+        for location in spec.locations {
+            if let Some(path) = location.find(binary, home) {
+                return Some(path);
             }
         }
+    }
+    None
+}
 
-        // Not fatal if missing, just log/warn? User request implies support is needed.
-        // If we want to be strict like Gemini:
-        if !found {
-            warn!("Codex CLI not found. Syncing to ~/.codex/skills will still occur, but the CLI might not be available.");
-            // We'll mark it as OK to avoid stopping the daemon, as Codex might be optional?
-            // But if the user strictly wants it, maybe we should warn louder.
-            // Given the user said "Any time... it needs to... installing... for codex", it sounds like the FILE sync is the priority.
-            // So I will set codex_cli_ok = true (or not block on it) but maybe log a warning.
-            // Actually, the prompt says "investgiate ... where skills are installed for codex cli".
-            // If I mark it false, I need to decide if main.rs should exit.
-            // For now, I'll mark it true but warn, so the daemon continues.
-            // Wait, if I mark it true here despite not finding it, I should just init it to true?
-            // No, let's track it accurately.
-            // I'll leave it as false if not found. And handle it in main.rs (warn but continue).
+/// Remembers detected versions per binary until the binary changes
+#[derive(Default)]
+pub struct VersionProbe {
+    cache: HashMap<PathBuf, (Option<SystemTime>, Option<Version>)>,
+}
+
+impl VersionProbe {
+    pub fn version(&mut self, binary: &Path, args: &[&str]) -> Option<Version> {
+        if args.is_empty() {
+            return None;
         }
-        found
-    };
-    // Initialize to found status
-    out.codex_cli_ok = codex_found;
+        let mtime = std::fs::metadata(binary).and_then(|m| m.modified()).ok();
+        if let Some((cached_mtime, version)) = self.cache.get(binary) {
+            if *cached_mtime == mtime {
+                return *version;
+            }
+        }
+        let version = detect_version(binary, args, VERSION_TIMEOUT);
+        self.cache.insert(binary.to_path_buf(), (mtime, version));
+        version
+    }
+}
 
-    // 4) Antigravity destination presence (directory)
-    let antigravity_dir = cfg
-        .destinations
-        .iter()
-        .find(|d| d.base_path.to_string_lossy().contains("antigravity/skills"))
-        .map(|d| d.base_path.as_path())
-        .unwrap_or(Path::new("/nonexistent"));
+/// Run `binary args` and parse a version from its output, killing it after `timeout`
+pub fn detect_version(binary: &Path, args: &[&str], timeout: Duration) -> Option<Version> {
+    let mut child = Command::new(binary)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
 
-    if antigravity_dir.exists() {
-        out.antigravity_ok = true;
-        info!(path = %antigravity_dir.display(), "Antigravity directory detected");
-    } else {
-        // Not fatal — we'll create it later — but surface as a warning so users can verify install
-        warn!(path = %antigravity_dir.display(), "Antigravity directory not found (will be created)");
-        out.antigravity_ok = true; // treat as OK since we can create it
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if started.elapsed() < timeout => {
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                warn!(binary = %binary.display(), "version check timed out");
+                return None;
+            }
+        }
     }
 
-    // 5) Codex directory presence (informational)
-    let codex_dir = cfg
-        .destinations
-        .iter()
-        .find(|d| d.base_path.to_string_lossy().contains(".codex/skills"))
-        .map(|d| d.base_path.as_path())
-        .unwrap_or(Path::new("/nonexistent"));
+    let mut output = String::new();
+    child.stdout.take()?.read_to_string(&mut output).ok()?;
+    Version::find(&output)
+}
 
-    if codex_dir.exists() {
-        info!(path = %codex_dir.display(), "Codex skills directory detected");
-    } else {
-        warn!(path = %codex_dir.display(), "Codex skills directory not found (will be created)");
+impl InstallLocation {
+    fn find(&self, binary: &str, home: &Path) -> Option<PathBuf> {
+        match self {
+            Self::BinDir(dir) => Some(expand(dir, home).join(binary)).filter(|p| p.exists()),
+            Self::VersionManager(root) => std::fs::read_dir(expand(root, home))
+                .ok()?
+                .flatten()
+                .map(|entry| entry.path().join("bin").join(binary))
+                .find(|path| path.exists()),
+        }
     }
+}
 
-    // 6) Cursor directory presence (informational)
-    let cursor_dir = cfg
-        .destinations
-        .iter()
-        .find(|d| d.base_path.to_string_lossy().contains(".cursor/skills"))
-        .map(|d| d.base_path.as_path())
-        .unwrap_or(Path::new("/nonexistent"));
+fn expand(path: &str, home: &Path) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => home.join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if cursor_dir.exists() {
-        info!(path = %cursor_dir.display(), "Cursor skills directory detected");
-        out.cursor_ok = true;
-    } else {
-        warn!(path = %cursor_dir.display(), "Cursor skills directory not found (will be created)");
-        out.cursor_ok = true; // treat as OK since we can create it
+    #[test]
+    fn test_locate_in_version_manager() {
+        let home = tempfile::TempDir::new().unwrap();
+        let bin = home.path().join(".nvm/versions/node/v20.1.0/bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::write(bin.join("skillsync-test-tool"), "").unwrap();
+
+        let spec = ToolSpec {
+            id: "test",
+            name: "Test",
+            binaries: &["skillsync-test-tool"],
+            locations: NODE_LOCATIONS,
+            version_args: &[],
+            skills_dirs: &[],
+            install_hint: "",
+        };
+        assert_eq!(
+            locate(&spec, home.path()),
+            Some(bin.join("skillsync-test-tool"))
+        );
     }
 
-    Ok(out)
+    #[test]
+    fn test_tool_for_matches_skills_dir() {
+        let home = Path::new("/Users/me");
+        let dest = Destination::new(
+            home.join(".gemini/antigravity/skills"),
+            crate::destination::DestinationType::ClaudeStyle,
+        );
+        assert_eq!(tool_for(home, &dest).map(|t| t.id), Some("antigravity"));
+        let other = Destination::new(
+            PathBuf::from("/elsewhere"),
+            crate::destination::DestinationType::ClaudeStyle,
+        );
+        assert!(tool_for(home, &other).is_none());
+    }
 }
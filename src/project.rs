@@ -0,0 +1,132 @@
+//! Project-local skills: `<repo>/.claude/skills` mirrored inside the repo
+//!
+//! Claude Code also reads skills from a repository's `.claude/skills`, which
+//! teammates on other tools never see. Each project found under a
+//! `[projects]` workspace or listed in `paths` gets its own `SkillSync`, with
+//! destinations relative to the project root and a manifest of its own so
+//! files skillsync did not write there are never removed.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, ProjectSettings};
+use crate::destination::Destination;
+use crate::manifest::Manifest;
+use crate::sync::SkillSync;
+use crate::trash::{Mirror, Restored};
+
+/// Skills directory of the project at `root`
+pub fn skills_dir(root: &Path) -> PathBuf {
+    root.join(".claude").join("skills")
+}
+
+/// Project roots to sync: every listed path, plus each directory under a
+/// workspace that has `.claude/skills`
+///
+/// The search stops at project roots, git repositories, hidden directories
+/// and `max_depth` levels below the workspace.
+pub fn discover(settings: &ProjectSettings) -> BTreeSet<PathBuf> {
+    let mut projects: BTreeSet<PathBuf> = settings
+        .paths
+        .iter()
+        .filter(|path| path.is_dir())
+        .cloned()
+        .collect();
+    for workspace in &settings.workspaces {
+        search(workspace, settings.max_depth, &mut projects);
+    }
+    projects
+}
+
+fn search(dir: &Path, depth: usize, projects: &mut BTreeSet<PathBuf>) {
+    if depth == 0 {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if hidden || !path.is_dir() {
+            continue;
+        }
+        if skills_dir(&path).is_dir() {
+            projects.insert(path);
+        } else if !path.join(".git").exists() {
+            search(&path, depth - 1, projects);
+        }
+    }
+}
+
+/// Project destinations, resolved against the project's root
+pub fn destinations(destinations: &[Destination], root: &Path) -> Vec<Destination> {
+    destinations
+        .iter()
+        .map(|dest| Destination {
+            base_path: root.join(&dest.base_path),
+            ..dest.clone()
+        })
+        .collect()
+}
+
+/// Where the manifest of the project at `root` is kept
+///
+/// The file is named after the canonical root with `%` and `/`
+/// percent-encoded, so distinct roots never share a manifest.
+pub fn manifest_file(config: &Config, root: &Path) -> PathBuf {
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let name = root
+        .to_string_lossy()
+        .trim_start_matches('/')
+        .replace('%', "%25")
+        .replace('/', "%2F");
+    config
+        .state_dir
+        .join("projects")
+        .join(format!("{}.json", name))
+}
+
+/// Sync manager for the project at `root`, writing to `enabled`, the
+/// project destinations whose tools are available
+/// (`preflight::enabled_project_destinations`)
+///
+/// Destination directories are only created once something is written to
+/// them, so a project is not filled with empty tool directories.
+pub fn project_sync(config: &Config, root: &Path, enabled: &[Destination]) -> SkillSync {
+    let mut sync = SkillSync::new(skills_dir(root), destinations(enabled, root))
+        .with_home(config.home.clone())
+        .with_manifest(Manifest::load(manifest_file(config, root)))
+        .with_deletion_guard(config.deletion_guard());
+    if config.trash.enabled {
+        sync = sync.with_trash(config.trash_store());
+    }
+    sync
+}
+
+/// The home source and every project with their destinations, so trashed
+/// entries of either can be restored
+pub fn mirrors(config: &Config) -> Vec<Mirror> {
+    let mut mirrors = vec![Mirror {
+        source: config.source.clone(),
+        destinations: config.destinations.clone(),
+    }];
+    mirrors.extend(discover(&config.projects).into_iter().map(|root| Mirror {
+        source: skills_dir(&root),
+        destinations: destinations(&config.projects.destinations, &root),
+    }));
+    mirrors
+}
+
+/// Record restored project entries in their project's manifest
+///
+/// For when no daemon is running; the daemon updates its live syncs instead.
+pub fn adopt_restored(config: &Config, restored: &Restored) {
+    for root in discover(&config.projects) {
+        if restored.paths.iter().any(|path| path.starts_with(&root)) {
+            project_sync(config, &root, &config.projects.destinations).adopt(&restored.paths);
+        }
+    }
+}
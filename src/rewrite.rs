@@ -0,0 +1,175 @@
+//! Point paths in mirrored skills at the copy they are read from
+//!
+//! A skill that runs `~/.claude/skills/foo/scripts/run.sh` or
+//! `${CLAUDE_SKILL_DIR}/scripts/run.sh` still reaches into the Claude copy
+//! once it lives in `~/.codex/skills/foo`. For Claude-style destinations
+//! every spelling of the source root is mapped to the destination root; for
+//! flattened outputs, which have no directory of their own, relative links
+//! are made absolute under the source skill instead.
+
+use std::path::Path;
+
+/// Variables Claude expands to the skill's own directory
+const SKILL_DIR_VARIABLES: &[&str] = &["${CLAUDE_SKILL_DIR}", "$CLAUDE_SKILL_DIR"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathRewrite {
+    /// Spellings of the source root and what each becomes, longest first
+    roots: Vec<(String, String)>,
+    /// Replacement for `${CLAUDE_SKILL_DIR}`
+    skill_dir: String,
+    /// Absolute directory relative Markdown links are resolved against
+    link_base: Option<String>,
+}
+
+impl PathRewrite {
+    /// For a skill copied from `source` into the `dest` directory tree
+    pub fn for_copy(home: &Path, source: &Path, dest: &Path, skill: &str) -> Self {
+        let dest_spellings = spellings(home, dest);
+        let mut roots: Vec<(String, String)> = spellings(home, source)
+            .into_iter()
+            .enumerate()
+            .map(|(i, from)| {
+                // Keep the spelling style (`~/`, `$HOME/`) when the destination allows it
+                let to = dest_spellings.get(i).unwrap_or(&dest_spellings[0]).clone();
+                (from, to)
+            })
+            .collect();
+        roots.sort_by_key(|(from, _)| std::cmp::Reverse(from.len()));
+        Self {
+            roots,
+            skill_dir: dest.join(skill).display().to_string(),
+            link_base: None,
+        }
+    }
+
+    /// For a flattened output rendered from the skill in `skill_dir`
+    pub fn for_flattened(skill_dir: &Path) -> Self {
+        let skill_dir = skill_dir.display().to_string();
+        Self {
+            roots: Vec::new(),
+            skill_dir: skill_dir.clone(),
+            link_base: Some(skill_dir),
+        }
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for variable in SKILL_DIR_VARIABLES {
+            text = text.replace(variable, &self.skill_dir);
+        }
+        for (from, to) in &self.roots {
+            text = replace_root(&text, from, to);
+        }
+        match &self.link_base {
+            Some(base) => absolutize_links(&text, base),
+            None => text,
+        }
+    }
+}
+
+/// Absolute path first, then `~/`, `$HOME/` and `${HOME}/` forms under `home`
+fn spellings(home: &Path, path: &Path) -> Vec<String> {
+    let mut spellings = vec![path.display().to_string()];
+    if let Ok(relative) = path.strip_prefix(home) {
+        let relative = relative.display();
+        for prefix in ["~", "$HOME", "${HOME}"] {
+            spellings.push(format!("{}/{}", prefix, relative));
+        }
+    }
+    spellings
+}
+
+/// Replace `from` where it is a whole path prefix, not e.g. `skills-old`
+/// or the tail of `/opt/home/me/.claude/skills`
+fn replace_root(text: &str, from: &str, to: &str) -> String {
+    let is_name = |c: char| c.is_alphanumeric() || matches!(c, '-' | '_' | '.');
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for (start, _) in text.match_indices(from) {
+        let end = start + from.len();
+        let inside_path = text[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| is_name(c) || c == '/');
+        let continues_name = text[end..].chars().next().is_some_and(is_name);
+        if !inside_path && !continues_name {
+            out.push_str(&text[last..start]);
+            out.push_str(to);
+            last = end;
+        }
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+/// Prefix relative `[text](target)` link targets with `base`
+pub(crate) fn absolutize_links(text: &str, base: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("](") {
+        out.push_str(&rest[..start + 2]);
+        rest = &rest[start + 2..];
+        let target = rest[..rest.find(')').unwrap_or(rest.len())].trim_start_matches('<');
+        let external = target.contains("://") || target.starts_with("mailto:");
+        if !target.is_empty() && !external && !target.starts_with(['/', '#', '~', '$']) {
+            if let Some(inner) = rest.strip_prefix('<') {
+                out.push('<');
+                rest = inner;
+            }
+            out.push_str(base);
+            out.push('/');
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_copy_maps_every_spelling_of_the_source_root() {
+        let home = PathBuf::from("/home/me");
+        let rewrite = PathRewrite::for_copy(
+            &home,
+            &home.join(".claude/skills"),
+            &home.join(".codex/skills"),
+            "foo",
+        );
+        assert_eq!(
+            rewrite.apply(
+                "~/.claude/skills/foo/run.sh $HOME/.claude/skills/foo \
+                 /home/me/.claude/skills ${CLAUDE_SKILL_DIR}/x ~/.claude/skills-old \
+                 /opt/home/me/.claude/skills"
+            ),
+            "~/.codex/skills/foo/run.sh $HOME/.codex/skills/foo \
+             /home/me/.codex/skills /home/me/.codex/skills/foo/x ~/.claude/skills-old \
+             /opt/home/me/.claude/skills"
+        );
+
+        // A destination outside the home directory is always spelled absolute
+        let rewrite = PathRewrite::for_copy(
+            &home,
+            &home.join(".claude/skills"),
+            Path::new("/srv/skills"),
+            "foo",
+        );
+        assert_eq!(rewrite.apply("~/.claude/skills/foo"), "/srv/skills/foo");
+    }
+
+    #[test]
+    fn test_flattened_links_point_at_the_source_skill() {
+        let rewrite = PathRewrite::for_flattened(Path::new("/home/me/.claude/skills/foo"));
+        assert_eq!(
+            rewrite.apply(
+                "[run](scripts/run.sh) [doc](https://x.dev) [top](#a) \
+                 [abs](/etc/hosts) ${CLAUDE_SKILL_DIR}/data"
+            ),
+            "[run](/home/me/.claude/skills/foo/scripts/run.sh) [doc](https://x.dev) [top](#a) \
+             [abs](/etc/hosts) /home/me/.claude/skills/foo/data"
+        );
+    }
+}
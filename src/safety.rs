@@ -0,0 +1,135 @@
+//! Mass-deletion safety brake
+//!
+//! A briefly emptied source (git checkout, dotfiles re-clone, unmounted
+//! volume) looks exactly like the user deleting every skill. When a single
+//! pass would remove too many skills, deletions are held back until the
+//! source comes back or the user confirms with `skillsync confirm-deletions`.
+//! Removals are counted over a time window, so a mass deletion that arrives
+//! in several batches is caught too.
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct DeletionGuard {
+    /// Hold when more than this fraction of known skills would be removed
+    pub max_fraction: f64,
+    /// Hold when more than this many skills would be removed
+    pub max_count: usize,
+    /// Marker file written by `skillsync confirm-deletions`
+    pub confirm_file: PathBuf,
+    /// Removals within this long of the first one count together
+    pub window: Duration,
+}
+
+/// Skills removed since the window opened, against the count at that time
+#[derive(Debug)]
+pub struct DeletionWindow {
+    opened: Instant,
+    known: usize,
+    removed: BTreeSet<String>,
+}
+
+impl DeletionGuard {
+    /// Whether removing `deleting` of `known` skills may proceed without confirmation
+    ///
+    /// Removing a single skill is always allowed, so deleting the last
+    /// remaining skill does not need confirmation.
+    pub fn allows(&self, deleting: usize, known: usize) -> bool {
+        if deleting <= 1 {
+            return true;
+        }
+        if deleting > self.max_count {
+            return false;
+        }
+        let fraction = deleting as f64 / known.max(deleting) as f64;
+        fraction <= self.max_fraction
+    }
+
+    /// Whether removing `skills` at `now` may proceed, counting the removals
+    /// allowed earlier in `window`
+    ///
+    /// An expired window reopens with `known()` skills; allowed removals are
+    /// added to it.
+    pub fn admit(
+        &self,
+        window: &mut Option<DeletionWindow>,
+        skills: &BTreeSet<String>,
+        known: impl FnOnce() -> usize,
+        now: Instant,
+    ) -> bool {
+        let current = match window {
+            Some(open) if now.duration_since(open.opened) < self.window => open,
+            _ => window.insert(DeletionWindow {
+                opened: now,
+                known: known(),
+                removed: BTreeSet::new(),
+            }),
+        };
+        let removed: BTreeSet<String> = current.removed.union(skills).cloned().collect();
+        if !self.allows(removed.len(), current.known) {
+            return false;
+        }
+        current.removed = removed;
+        true
+    }
+}
+
+impl DeletionWindow {
+    /// Skills removed in the window so far
+    pub fn removed(&self) -> usize {
+        self.removed.len()
+    }
+
+    /// Skills that existed when the window opened
+    pub fn known(&self) -> usize {
+        self.known
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guard() -> DeletionGuard {
+        DeletionGuard {
+            max_fraction: 0.5,
+            max_count: 5,
+            confirm_file: PathBuf::from("/nonexistent"),
+            window: Duration::from_secs(60),
+        }
+    }
+
+    fn skills(names: &[&str]) -> BTreeSet<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_allows_small_deletions() {
+        assert!(guard().allows(0, 0));
+        assert!(guard().allows(1, 1));
+        assert!(guard().allows(2, 4));
+    }
+
+    #[test]
+    fn test_holds_large_fraction_or_count() {
+        assert!(!guard().allows(3, 4));
+        assert!(!guard().allows(6, 100));
+    }
+
+    #[test]
+    fn test_window_counts_removals_until_it_expires() {
+        let guard = guard();
+        let start = Instant::now();
+        let mut window = None;
+        assert!(guard.admit(&mut window, &skills(&["a", "b"]), || 6, start));
+        // Two more within the window make 4 of 6
+        assert!(!guard.admit(&mut window, &skills(&["c", "d"]), || 4, start));
+        assert_eq!(window.as_ref().unwrap().removed(), 2);
+
+        let later = start + Duration::from_secs(61);
+        assert!(guard.admit(&mut window, &skills(&["c", "d"]), || 4, later));
+        assert_eq!(window.as_ref().unwrap().known(), 4);
+    }
+}
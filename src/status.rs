@@ -0,0 +1,131 @@
+//! Machine-readable status snapshot of the daemon
+//!
+//! The snapshot is rewritten to `<state>/status.json` after every sync pass
+//! and watcher state change, so `skillsync status --json` works even when the
+//! daemon is wedged or not running.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tracing::warn;
+
+use crate::clock::{unix_secs, utc_timestamp};
+use crate::persist::write_json_atomic;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Status {
+    pub pid: u32,
+    pub started_at: String,
+    /// When this snapshot was written
+    pub updated_at: String,
+    pub uptime_secs: u64,
+    /// Completion time of the last pass that finished without errors
+    pub last_successful_sync: Option<String>,
+    /// `initial`, `batch` or `reconcile`
+    pub last_sync_kind: Option<String>,
+    /// Skill count per destination base path
+    pub destinations: BTreeMap<String, usize>,
+    /// Latest unresolved error per source path
+    pub errors: BTreeMap<String, PathError>,
+    pub held_deletions: Vec<String>,
+    pub watcher: WatcherHealth,
+    /// Detected tools by id
+    #[serde(default)]
+    pub tools: BTreeMap<String, ToolStatus>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PathError {
+    pub error: String,
+    pub at: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WatcherHealth {
+    pub source_watched: bool,
+    pub paused: bool,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolStatus {
+    pub installed: bool,
+    pub binary: Option<String>,
+    pub version: Option<String>,
+}
+
+/// Owner of the status snapshot and the file it is persisted to
+pub struct StatusFile {
+    path: PathBuf,
+    started: SystemTime,
+    status: Mutex<Status>,
+}
+
+impl StatusFile {
+    pub fn new(path: PathBuf) -> Self {
+        let started = SystemTime::now();
+        let status = Status {
+            pid: std::process::id(),
+            started_at: utc_timestamp(started),
+            ..Status::default()
+        };
+        Self {
+            path,
+            started,
+            status: Mutex::new(status),
+        }
+    }
+
+    /// Apply `change` and persist the result
+    ///
+    /// Failing to write the snapshot is logged, never fatal.
+    pub fn update(&self, change: impl FnOnce(&mut Status)) {
+        let mut status = self.status.lock().unwrap();
+        change(&mut status);
+
+        let now = SystemTime::now();
+        status.updated_at = utc_timestamp(now);
+        status.uptime_secs = unix_secs(now).saturating_sub(unix_secs(self.started));
+
+        if let Err(e) = write_json_atomic(&self.path, &*status) {
+            warn!(path = %self.path.display(), error = %e, "failed to write status file");
+        }
+    }
+
+    pub fn snapshot(&self) -> Status {
+        self.status.lock().unwrap().clone()
+    }
+
+    /// Read a snapshot written by a (possibly different) process
+    pub fn read(path: &Path) -> Result<Status> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("No status at {} (has the daemon run?)", path.display()))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("Invalid status file {}", path.display()))
+    }
+}
+
+impl Status {
+    pub fn record_error(&mut self, path: &Path, error: &anyhow::Error) {
+        self.errors.insert(
+            path.display().to_string(),
+            PathError {
+                error: format!("{:#}", error),
+                at: utc_timestamp(SystemTime::now()),
+            },
+        );
+    }
+
+    pub fn clear_error(&mut self, path: &Path) {
+        self.errors.remove(&path.display().to_string());
+    }
+
+    pub fn record_success(&mut self, kind: &str) {
+        self.last_successful_sync = Some(utc_timestamp(SystemTime::now()));
+        self.last_sync_kind = Some(kind.to_string());
+    }
+}
@@ -1,24 +1,252 @@
 //! File synchronization logic
 
 use anyhow::{Context, Result};
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
+use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
-use tracing::{info, warn};
+use std::sync::Mutex;
+use std::time::Instant;
+use tracing::{error, info, warn};
 
 use crate::destination::{Destination, DestinationType};
-use crate::transform::{generate_toml, parse_frontmatter};
+use crate::inline::inline_references;
+use crate::manifest::Manifest;
+use crate::preflight;
+use crate::rewrite::PathRewrite;
+use crate::safety::{DeletionGuard, DeletionWindow};
+use crate::status::{Status, StatusFile};
+use crate::transform::{
+    adapt_skill_md, apply_template, generate_toml, parse_frontmatter, tool_description,
+    SkillTargets, TemplateContext,
+};
+use crate::trash::{skill_name_in, Trash};
 
 pub struct SkillSync {
-    source: PathBuf,
+    /// Source roots, highest precedence first; merged by skill name
+    sources: Vec<PathBuf>,
+    /// Home directory, for `{{ home }}` in skill templates
+    home: PathBuf,
     destinations: Vec<Destination>,
+    trash: Option<Trash>,
+    guard: Option<DeletionGuard>,
+    status: Option<StatusFile>,
+    /// When set, only skills recorded here are removed as orphans
+    manifest: Option<Manifest>,
+    /// Skills whose destination copies are kept until the hold is released
+    held_deletions: Mutex<BTreeSet<String>>,
+    /// Removals the deletion guard has allowed recently
+    deletion_window: Mutex<Option<DeletionWindow>>,
+    /// Source directory inode per skill, for rename detection
+    skill_inodes: Mutex<HashMap<String, u64>>,
+    /// Trash snapshot of the current pass, allocated on its first removal
+    trash_snapshot: Mutex<Option<String>>,
+    /// Destination entries created since the manifest was last updated
+    created: Mutex<BTreeSet<(PathBuf, String)>>,
+}
+
+/// Keeps the removals of one pass in a single trash snapshot
+struct Pass<'a>(&'a Mutex<Option<String>>);
+
+impl Drop for Pass<'_> {
+    fn drop(&mut self) {
+        *self.0.lock().unwrap() = None;
+    }
+}
+
+/// A destination entry with no counterpart in the source
+struct Orphan {
+    path: PathBuf,
+    skill: String,
+    display_name: String,
 }
 
 impl SkillSync {
     pub fn new(source: PathBuf, destinations: Vec<Destination>) -> Self {
         Self {
-            source,
+            sources: vec![source],
+            home: home::home_dir().unwrap_or_default(),
             destinations,
+            trash: None,
+            guard: None,
+            status: None,
+            manifest: None,
+            held_deletions: Mutex::new(BTreeSet::new()),
+            deletion_window: Mutex::new(None),
+            skill_inodes: Mutex::new(HashMap::new()),
+            trash_snapshot: Mutex::new(None),
+            created: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    /// Merge several source roots, highest precedence first
+    ///
+    /// A skill in an earlier root hides the skill of the same name in every
+    /// later one. An empty list keeps the current sources.
+    pub fn with_sources(mut self, sources: Vec<PathBuf>) -> Self {
+        if !sources.is_empty() {
+            self.sources = sources;
+        }
+        self
+    }
+
+    pub fn sources(&self) -> &[PathBuf] {
+        &self.sources
+    }
+
+    /// Home directory templates see, instead of the current user's
+    pub fn with_home(mut self, home: PathBuf) -> Self {
+        self.home = home;
+        self
+    }
+
+    /// Hold back passes that would delete too many skills at once
+    pub fn with_deletion_guard(mut self, guard: DeletionGuard) -> Self {
+        self.guard = Some(guard);
+        self
+    }
+
+    /// Keep a status snapshot up to date after every pass
+    pub fn with_status(mut self, status: StatusFile) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn status(&self) -> Option<&StatusFile> {
+        self.status.as_ref()
+    }
+
+    /// Only remove destination entries this sync is recorded to have written
+    pub fn with_manifest(mut self, manifest: Manifest) -> Self {
+        self.manifest = Some(manifest);
+        self
+    }
+
+    /// Whether `skill` in `dest` came from this sync; always true without a manifest
+    fn owns(&self, dest: &Destination, skill: &str) -> bool {
+        self.manifest
+            .as_ref()
+            .is_none_or(|manifest| manifest.owns(&dest.base_path, skill))
+    }
+
+    /// Whether this sync may write `skill` into `dest`
+    ///
+    /// With a manifest, an existing entry it does not own (e.g. a
+    /// hand-written `build.toml`) is left alone. Entries this call lets the
+    /// sync create are recorded as owned by the next `update_manifest`.
+    fn may_write(&self, dest: &Destination, skill: &str) -> bool {
+        let Some(manifest) = &self.manifest else {
+            return true;
+        };
+        if manifest.owns(&dest.base_path, skill) {
+            return true;
+        }
+        let mut created = self.created.lock().unwrap();
+        let key = (dest.base_path.clone(), skill.to_string());
+        if created.contains(&key) {
+            return true;
         }
+        let output = skill_output(dest, skill);
+        if output.exists() {
+            warn!(
+                path = %output.display(),
+                "not overwriting destination entry skillsync did not create"
+            );
+            return false;
+        }
+        created.insert(key);
+        true
+    }
+
+    /// Record the entries this sync created in each destination
+    ///
+    /// Entries listed before stay listed while they exist, so a skill that
+    /// left the source is still recognised as an orphan.
+    fn update_manifest(&self) {
+        let Some(manifest) = &self.manifest else {
+            return;
+        };
+        let created = std::mem::take(&mut *self.created.lock().unwrap());
+        for dest in &self.destinations {
+            let mut owned = manifest.skills(&dest.base_path);
+            owned.extend(
+                created
+                    .iter()
+                    .filter(|(base, _)| *base == dest.base_path)
+                    .map(|(_, skill)| skill.clone()),
+            );
+            let present = dest_skills(dest);
+            owned.retain(|skill| present.contains(skill));
+            manifest.set(&dest.base_path, owned);
+        }
+    }
+
+    /// Record restored destination entries as written by this sync
+    ///
+    /// The manifest forgot them when they went to the trash; without this a
+    /// restored skill would never be updated or removed again.
+    pub fn adopt(&self, restored: &[PathBuf]) {
+        if self.manifest.is_none() {
+            return;
+        }
+        {
+            let mut created = self.created.lock().unwrap();
+            for dest in &self.destinations {
+                for path in restored {
+                    if let Some(skill) = skill_name_in(dest, path) {
+                        created.insert((dest.base_path.clone(), skill));
+                    }
+                }
+            }
+        }
+        self.update_manifest();
+    }
+
+    /// Apply `change` to the status snapshot, refreshing destination counts
+    fn update_status(&self, change: impl FnOnce(&mut Status)) {
+        let Some(status_file) = &self.status else {
+            return;
+        };
+        let destinations = self
+            .destinations
+            .iter()
+            .map(|dest| {
+                (
+                    dest.base_path.display().to_string(),
+                    dest_skills(dest).len(),
+                )
+            })
+            .collect();
+        let held = self.held_deletions();
+        status_file.update(|status| {
+            change(status);
+            status.destinations = destinations;
+            status.held_deletions = held;
+        });
+    }
+
+    /// Move removed destination content into `trash` instead of deleting it
+    pub fn with_trash(mut self, trash: Trash) -> Self {
+        self.trash = Some(trash);
+        self
+    }
+
+    /// Remove a destination file or directory, via the trash when configured
+    fn discard(&self, path: &Path) -> Result<()> {
+        if let Some(trash) = &self.trash {
+            let id = self
+                .trash_snapshot
+                .lock()
+                .unwrap()
+                .get_or_insert_with(|| trash.snapshot_id())
+                .clone();
+            trash.discard(path, &id)?;
+        } else if path.is_dir() {
+            fs::remove_dir_all(path)?;
+        } else {
+            fs::remove_file(path)?;
+        }
+        Ok(())
     }
 
     /// Ensure all required directories exist
@@ -39,23 +267,68 @@ impl SkillSync {
 
     /// Perform initial full sync from source to all destinations
     pub fn initial_sync(&self) -> Result<()> {
+        let _pass = Pass(&self.trash_snapshot);
+        let result = self.initial_sync_inner();
+        self.update_manifest();
+        self.update_status(|status| match &result {
+            Ok(failed) => {
+                status.clear_error(&self.sources[0]);
+                if *failed == 0 {
+                    status.record_success("initial");
+                }
+            }
+            Err(e) => status.record_error(&self.sources[0], e),
+        });
+        result.map(|_| ())
+    }
+
+    /// Returns the number of skills that failed to sync
+    fn initial_sync_inner(&self) -> Result<usize> {
         info!("starting initial sync");
 
-        if !self.source.exists() {
+        let missing = self.missing_sources();
+        for root in &missing {
             warn!(
-                path = %self.source.display(),
+                path = %root.display(),
                 "source directory does not exist, waiting for creation"
             );
-            return Ok(());
+        }
+        if missing.len() == self.sources.len() {
+            return Ok(0);
         }
 
-        self.sync_directory(&self.source)?;
+        // One broken skill must not keep the others from syncing
+        let mut failures = Vec::new();
+        for skill in self.source_skills() {
+            let path = self.skill_path(&skill);
+            let result = if path.is_dir() {
+                self.sync_directory(&path)
+            } else {
+                self.sync_file(&path)
+            };
+            if let Err(e) = result {
+                warn!(path = %path.display(), error = %format!("{:#}", e), "failed to sync");
+                failures.push((path, e));
+            }
+        }
+        self.update_status(|status| {
+            for (path, e) in &failures {
+                status.record_error(path, e);
+            }
+        });
 
-        // Clean up orphaned files in all destinations
-        self.cleanup_orphans()?;
+        // Clean up orphaned files in all destinations; with a source missing
+        // its skills would all look orphaned
+        if missing.is_empty() {
+            self.cleanup_orphans()?;
+        }
 
-        info!("initial sync completed");
-        Ok(())
+        if let Some(trash) = &self.trash {
+            trash.prune()?;
+        }
+
+        info!(failed = failures.len(), "initial sync completed");
+        Ok(failures.len())
     }
 
     /// Recursively sync a directory
@@ -80,129 +353,435 @@ impl SkillSync {
 
     /// Sync a single file from source to all destinations
     fn sync_file(&self, source_path: &Path) -> Result<()> {
-        let relative = source_path
-            .strip_prefix(&self.source)
-            .with_context(|| format!("Path {} is not under source", source_path.display()))?;
+        let relative = self.relative(source_path)?;
+
+        for (dest_path, content) in self.render_outputs(source_path)? {
+            // Create parent directories if needed
+            if let Some(parent) = dest_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&dest_path, content)
+                .with_context(|| format!("Failed to write to {}", dest_path.display()))?;
+        }
+
+        if relative.components().count() > 1 {
+            self.remember_skill(&skill_of(relative));
+        }
+
+        info!(file = %relative.display(), "synced");
+        Ok(())
+    }
+
+    /// Destination paths and contents a source file maps to
+    fn render_outputs(&self, source_path: &Path) -> Result<Vec<(PathBuf, String)>> {
+        self.render_outputs_to(&self.destinations, source_path)
+    }
+
+    fn render_outputs_to(
+        &self,
+        destinations: &[Destination],
+        source_path: &Path,
+    ) -> Result<Vec<(PathBuf, String)>> {
+        let relative = self.relative(source_path)?;
 
         // Read source file content once
         let source_content = fs::read_to_string(source_path)
             .with_context(|| format!("Failed to read {}", source_path.display()))?;
 
-        for dest in &self.destinations {
+        let targets = if relative.components().count() > 1 {
+            self.skill_targets(&skill_of(relative))
+        } else {
+            SkillTargets::default()
+        };
+
+        let is_skill_md = source_path.file_name() == Some(std::ffi::OsStr::new("SKILL.md"));
+        let mut outputs = Vec::new();
+        for dest in destinations {
+            if !targets.allows(dest.tool.as_deref()) {
+                continue;
+            }
             match dest.dest_type {
                 DestinationType::ClaudeStyle => {
-                    // Direct copy with same structure
-                    let dest_path = dest.base_path.join(relative);
-
-                    // Create parent directories if needed
-                    if let Some(parent) = dest_path.parent() {
-                        fs::create_dir_all(parent)?;
+                    // Same structure; SKILL.md templated and adapted to the tool
+                    let skill = skill_of(relative);
+                    if !self.may_write(dest, &skill) {
+                        continue;
                     }
-
-                    // Write original content
-                    fs::write(&dest_path, &source_content)
-                        .with_context(|| format!("Failed to write to {}", dest_path.display()))?;
+                    let content = if is_skill_md {
+                        render_skill_md(&self.templated(&source_content, dest, &skill)?, dest)
+                    } else {
+                        source_content.clone()
+                    };
+                    let content = self.rewrite_paths(content, dest, &skill);
+                    outputs.push((dest.base_path.join(relative), content));
                 }
                 DestinationType::GeminiToml => {
                     // Transform to TOML format
-                    // Only process SKILL.md files
-                    if source_path.file_name() != Some(std::ffi::OsStr::new("SKILL.md")) {
+                    // Only SKILL.md maps to a command, so it is rendered once
+                    // per skill; a batch touching any file of a skill syncs
+                    // the whole skill, which picks up inlined references
+                    if !is_skill_md {
                         continue;
                     }
+                    let skill_dir = source_path.parent().unwrap_or(source_path);
 
                     // Get the parent directory name (e.g., "ui-skills" from "ui-skills/SKILL.md")
-                    let skill_name = relative
-                        .parent()
-                        .and_then(|p| p.file_name())
+                    let skill_name = skill_dir
+                        .file_name()
                         .and_then(|n| n.to_str())
                         .unwrap_or("skill");
+                    if !self.may_write(dest, skill_name) {
+                        continue;
+                    }
+
+                    // Written as {skill_name}.toml in commands directory
+                    outputs.push((
+                        dest.base_path.join(format!("{}.toml", skill_name)),
+                        render_toml(
+                            &self.templated(&source_content, dest, skill_name)?,
+                            dest,
+                            skill_dir,
+                        ),
+                    ));
+                }
+            }
+        }
+
+        Ok(outputs)
+    }
+
+    /// SKILL.md with its template (if it opted in) rendered for `dest`
+    fn templated(&self, content: &str, dest: &Destination, skill: &str) -> Result<String> {
+        let ctx = template_context(&self.home, dest, skill);
+        apply_template(content, &ctx).with_context(|| {
+            format!(
+                "Failed to render template of {} for {}",
+                skill,
+                dest.base_path.display()
+            )
+        })
+    }
+
+    /// `content` with source paths pointed at `dest`, if it rewrites paths
+    fn rewrite_paths(&self, content: String, dest: &Destination, skill: &str) -> String {
+        if !dest.rewrite_paths {
+            return content;
+        }
+        PathRewrite::for_copy(&self.home, self.root_of(skill), &dest.base_path, skill)
+            .apply(&content)
+    }
 
-                    // Parse frontmatter and generate TOML
-                    let (frontmatter, stripped_content) = parse_frontmatter(&source_content);
-                    let toml_content = generate_toml(frontmatter.description, &stripped_content);
+    /// Source root providing `skill`: the first one that contains it
+    fn root_of(&self, skill: &str) -> &Path {
+        self.sources
+            .iter()
+            .find(|root| root.join(skill).exists())
+            .unwrap_or(&self.sources[0])
+    }
+
+    /// Where a skill of the merged tree lives on disk
+    fn skill_path(&self, skill: &str) -> PathBuf {
+        self.root_of(skill).join(skill)
+    }
+
+    /// Where a path relative to the merged tree lives on disk
+    fn source_path(&self, relative: &Path) -> PathBuf {
+        self.root_of(&skill_of(relative)).join(relative)
+    }
+
+    /// `path` relative to the source root it is under
+    fn relative<'a>(&self, path: &'a Path) -> Result<&'a Path> {
+        self.sources
+            .iter()
+            .find_map(|root| path.strip_prefix(root).ok())
+            .with_context(|| format!("Path {} is not under source", path.display()))
+    }
+
+    /// Source roots that do not exist right now
+    fn missing_sources(&self) -> Vec<&PathBuf> {
+        self.sources.iter().filter(|root| !root.exists()).collect()
+    }
 
-                    // Write as {skill_name}.toml in commands directory
-                    let dest_path = dest.base_path.join(format!("{}.toml", skill_name));
-                    fs::write(&dest_path, toml_content).with_context(|| {
-                        format!("Failed to write TOML to {}", dest_path.display())
-                    })?;
+    /// A skill's `skillsync:` frontmatter targets; unrestricted without SKILL.md
+    fn skill_targets(&self, skill: &str) -> SkillTargets {
+        fs::read_to_string(self.skill_path(skill).join("SKILL.md"))
+            .map(|content| parse_frontmatter(&content).0.targets)
+            .unwrap_or_default()
+    }
+
+    pub fn destinations(&self) -> &[Destination] {
+        &self.destinations
+    }
+
+    /// Swap in a new source list, e.g. after the config file changed
+    ///
+    /// Nothing is written here; the next `reconcile` renders skills that now
+    /// win and removes the ones no source provides any more.
+    pub fn set_sources(&mut self, sources: Vec<PathBuf>) {
+        if !sources.is_empty() {
+            self.sources = sources;
+        }
+        self.skill_inodes.lock().unwrap().clear();
+    }
+
+    /// Swap in a new destination list, e.g. after the config file changed
+    ///
+    /// Destinations are matched by path and type. Added ones, and ones whose
+    /// settings changed, are filled from the source right away. Removed
+    /// ones keep their content unless `clean_removed` is set, in which case
+    /// every skill the source put there is discarded.
+    pub fn set_destinations(
+        &mut self,
+        destinations: Vec<Destination>,
+        clean_removed: bool,
+    ) -> Result<()> {
+        let _pass = Pass(&self.trash_snapshot);
+        let same = |a: &Destination, b: &Destination| {
+            a.base_path == b.base_path && a.dest_type == b.dest_type
+        };
+        let changed: Vec<Destination> = destinations
+            .iter()
+            .filter(|dest| !self.destinations.contains(dest))
+            .cloned()
+            .collect();
+        let removed: Vec<Destination> = self
+            .destinations
+            .iter()
+            .filter(|dest| !destinations.iter().any(|new| same(dest, new)))
+            .cloned()
+            .collect();
+        // Fill before swapping, so a failure keeps the current list
+        for dest in &changed {
+            if self.destinations.iter().any(|old| same(old, dest)) {
+                info!(path = %dest.base_path.display(), "destination settings changed");
+            } else {
+                info!(path = %dest.base_path.display(), "destination added");
+            }
+            fs::create_dir_all(&dest.base_path).with_context(|| {
+                format!("Failed to create destination: {}", dest.base_path.display())
+            })?;
+            for skill in self.source_skills() {
+                self.fill_destination(dest, &self.skill_path(&skill))?;
+            }
+        }
+        self.destinations = destinations;
+
+        for dest in &removed {
+            info!(path = %dest.base_path.display(), clean = clean_removed, "destination removed");
+            if clean_removed {
+                for skill in self.source_skills() {
+                    let dest_path = skill_output(dest, &skill);
+                    if dest_path.exists() && self.owns(dest, &skill) {
+                        self.discard(&dest_path)?;
+                    }
                 }
             }
         }
 
-        info!(file = %relative.display(), "synced");
+        self.update_manifest();
+        self.update_status(|_| {});
         Ok(())
     }
 
-    /// Remove a file from all destinations
-    fn remove_file(&self, source_path: &Path) -> Result<()> {
-        let relative = source_path
-            .strip_prefix(&self.source)
-            .with_context(|| format!("Path {} is not under source", source_path.display()))?;
+    /// Write the source file or everything under the directory `path` to a
+    /// single destination
+    fn fill_destination(&self, dest: &Destination, path: &Path) -> Result<()> {
+        if path.is_dir() {
+            for entry in fs::read_dir(path)? {
+                self.fill_destination(dest, &entry?.path())?;
+            }
+            return Ok(());
+        }
+        for (dest_path, content) in self.render_outputs_to(std::slice::from_ref(dest), path)? {
+            if let Some(parent) = dest_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&dest_path, content)
+                .with_context(|| format!("Failed to write to {}", dest_path.display()))?;
+        }
+        Ok(())
+    }
 
-        for dest in &self.destinations {
-            match dest.dest_type {
-                DestinationType::ClaudeStyle => {
-                    let dest_path = dest.base_path.join(relative);
-
-                    if dest_path.exists() {
-                        if dest_path.is_dir() {
-                            fs::remove_dir_all(&dest_path)?;
-                        } else {
-                            fs::remove_file(&dest_path)?;
-                        }
-                    }
+    /// Compare every destination against the source and repair what differs
+    ///
+    /// Unlike `initial_sync`, files that already match are not rewritten, so
+    /// this is cheap enough to run periodically. Returns the number of
+    /// corrections made.
+    pub fn reconcile(&self) -> Result<usize> {
+        let _pass = Pass(&self.trash_snapshot);
+        let result = self.reconcile_inner();
+        self.update_manifest();
+        self.update_status(|status| match &result {
+            Ok((_, failed)) => {
+                status.clear_error(&self.sources[0]);
+                if *failed == 0 {
+                    status.record_success("reconcile");
                 }
-                DestinationType::GeminiToml => {
-                    // Only handle SKILL.md files
-                    if source_path.file_name() != Some(std::ffi::OsStr::new("SKILL.md")) {
-                        continue;
-                    }
+            }
+            Err(e) => status.record_error(&self.sources[0], e),
+        });
+        result.map(|(corrections, _)| corrections)
+    }
 
-                    // Get skill name from parent directory
-                    let skill_name = relative
-                        .parent()
-                        .and_then(|p| p.file_name())
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("skill");
+    /// Returns the corrections made and the number of skills that failed to render
+    fn reconcile_inner(&self) -> Result<(usize, usize)> {
+        let missing = self.missing_sources();
+        if missing.len() == self.sources.len() {
+            return Ok((0, 0));
+        }
 
-                    // Remove the corresponding .toml file
-                    let dest_path = dest.base_path.join(format!("{}.toml", skill_name));
-                    if dest_path.exists() {
-                        fs::remove_file(&dest_path)?;
-                    }
+        let started = Instant::now();
+        let mut corrections = 0;
+        let mut rendered = Vec::new();
+        for skill in self.source_skills() {
+            let path = self.skill_path(&skill);
+            let mut failure = None;
+            self.reconcile_path(&path, &mut corrections, &mut failure)?;
+            rendered.push((path, failure));
+        }
+        self.update_status(|status| {
+            for (path, failure) in &rendered {
+                match failure {
+                    Some(e) => status.record_error(path, e),
+                    None => status.clear_error(path),
                 }
             }
+        });
+
+        if missing.is_empty() {
+            corrections += self.cleanup_orphans()?;
+        } else {
+            warn!(missing = ?missing, "source directory missing, skipping orphan cleanup");
+        }
+
+        if let Some(trash) = &self.trash {
+            trash.prune()?;
+        }
+
+        let failed = rendered.iter().filter(|(_, f)| f.is_some()).count();
+        info!(
+            corrections,
+            failed,
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "reconciliation completed"
+        );
+        Ok((corrections, failed))
+    }
+
+    /// Repair the outputs of the source file or everything under the directory `path`
+    ///
+    /// The first file that fails to render is kept in `failure`.
+    fn reconcile_path(
+        &self,
+        path: &Path,
+        corrections: &mut usize,
+        failure: &mut Option<anyhow::Error>,
+    ) -> Result<()> {
+        if path.is_dir() {
+            for entry in fs::read_dir(path)? {
+                self.reconcile_path(&entry?.path(), corrections, failure)?;
+            }
+            return Ok(());
         }
 
-        info!(file = %relative.display(), "removed");
+        // A skill that fails to render (e.g. a template error) is left as
+        // it is rather than stopping the whole pass
+        let outputs = match self.render_outputs(path) {
+            Ok(outputs) => outputs,
+            Err(e) => {
+                warn!(file = %path.display(), error = %format!("{:#}", e), "failed to render");
+                failure.get_or_insert(e);
+                return Ok(());
+            }
+        };
+        for (dest_path, content) in outputs {
+            let current = fs::read_to_string(&dest_path).ok();
+            if current.as_deref() == Some(content.as_str()) {
+                continue;
+            }
+            if let Some(parent) = dest_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&dest_path, content)
+                .with_context(|| format!("Failed to write to {}", dest_path.display()))?;
+            warn!(
+                file = %dest_path.display(),
+                reason = if current.is_some() { "stale" } else { "missing" },
+                "reconciled drifted destination"
+            );
+            *corrections += 1;
+        }
         Ok(())
     }
 
+    /// Destination entries with no counterpart in the source
+    pub fn count_orphans(&self) -> Result<usize> {
+        Ok(self.find_orphans()?.len())
+    }
+
+    /// Record the inode of a skill directory so a later rename can be recognised
+    fn remember_skill(&self, skill: &str) {
+        if let Ok(meta) = fs::metadata(self.skill_path(skill)) {
+            self.skill_inodes
+                .lock()
+                .unwrap()
+                .insert(skill.to_string(), meta.ino());
+        }
+    }
+
     /// Remove orphaned files/directories in all destinations that don't exist in source
-    fn cleanup_orphans(&self) -> Result<()> {
+    ///
+    /// Returns the number of entries removed.
+    fn cleanup_orphans(&self) -> Result<usize> {
+        let orphans = self.find_orphans()?;
+        let skills: BTreeSet<String> = orphans.iter().map(|o| o.skill.clone()).collect();
+        if !self.deletion_allowed(&skills) {
+            return Ok(0);
+        }
+        self.discard_orphans(orphans)
+    }
+
+    fn discard_orphans(&self, orphans: Vec<Orphan>) -> Result<usize> {
+        let mut removed = 0;
+        for orphan in orphans {
+            if !orphan.path.exists() {
+                continue;
+            }
+            self.discard(&orphan.path)?;
+            info!(file = %orphan.display_name, "removed orphan");
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    fn find_orphans(&self) -> Result<Vec<Orphan>> {
+        let mut orphans = Vec::new();
         for dest in &self.destinations {
-            self.cleanup_orphans_for_dest(dest)?;
+            self.find_orphans_for_dest(dest, &mut orphans)?;
         }
-        Ok(())
+        Ok(orphans)
     }
 
-    fn cleanup_orphans_for_dest(&self, dest: &Destination) -> Result<()> {
+    fn find_orphans_for_dest(&self, dest: &Destination, orphans: &mut Vec<Orphan>) -> Result<()> {
         match dest.dest_type {
             DestinationType::ClaudeStyle => {
-                self.cleanup_orphans_recursive_claude(&dest.base_path, &dest.base_path)
+                self.find_orphans_recursive_claude(dest, &dest.base_path, orphans)
             }
-            DestinationType::GeminiToml => self.cleanup_orphans_toml(&dest.base_path),
+            DestinationType::GeminiToml => self.find_orphans_toml(dest, orphans),
         }
     }
 
-    fn cleanup_orphans_recursive_claude(&self, dest_root: &Path, dest_dir: &Path) -> Result<()> {
-        if !dest_dir.exists() {
+    fn find_orphans_recursive_claude(
+        &self,
+        dest: &Destination,
+        dest_dir: &Path,
+        orphans: &mut Vec<Orphan>,
+    ) -> Result<()> {
+        let dest_root = &dest.base_path;
+        if !dest_dir.is_dir() {
             return Ok(());
         }
 
-        let mut entries_to_remove = Vec::new();
-
         for entry in fs::read_dir(dest_dir)? {
             let entry = entry?;
             let dest_path = entry.path();
@@ -211,29 +790,33 @@ impl SkillSync {
                 .strip_prefix(dest_root)
                 .context("Invalid destination path")?;
 
-            let source_path = self.source.join(relative);
-
-            if !source_path.exists() {
-                entries_to_remove.push(dest_path.clone());
-            } else if dest_path.is_dir() {
-                self.cleanup_orphans_recursive_claude(dest_root, &dest_path)?;
+            if relative.components().count() == 1 && !self.owns(dest, &skill_of(relative)) {
+                continue;
             }
-        }
+            let source_path = self.source_path(relative);
+            // A skill directory whose frontmatter has since excluded this destination
+            let excluded = dest_path.is_dir()
+                && relative.components().count() == 1
+                && !self
+                    .skill_targets(&skill_of(relative))
+                    .allows(dest.tool.as_deref());
 
-        for path in entries_to_remove {
-            let relative = path.strip_prefix(dest_root).unwrap_or(&path);
-            if path.is_dir() {
-                fs::remove_dir_all(&path)?;
-            } else {
-                fs::remove_file(&path)?;
+            if !source_path.exists() || excluded {
+                orphans.push(Orphan {
+                    skill: skill_of(relative),
+                    display_name: relative.display().to_string(),
+                    path: dest_path.clone(),
+                });
+            } else if dest_path.is_dir() {
+                self.find_orphans_recursive_claude(dest, &dest_path, orphans)?;
             }
-            info!(file = %relative.display(), "removed orphan");
         }
 
         Ok(())
     }
 
-    fn cleanup_orphans_toml(&self, dest_dir: &Path) -> Result<()> {
+    fn find_orphans_toml(&self, dest: &Destination, orphans: &mut Vec<Orphan>) -> Result<()> {
+        let dest_dir = &dest.base_path;
         if !dest_dir.exists() {
             return Ok(());
         }
@@ -250,15 +833,19 @@ impl SkillSync {
 
             // Get the skill name from filename (e.g., "ui-skills.toml" -> "ui-skills")
             if let Some(skill_name) = dest_path.file_stem().and_then(|s| s.to_str()) {
+                if !self.owns(dest, skill_name) {
+                    continue;
+                }
                 // Check if corresponding SKILL.md exists in source
-                let source_skill_path = self.source.join(skill_name).join("SKILL.md");
-
-                if !source_skill_path.exists() {
-                    fs::remove_file(&dest_path)?;
-                    info!(
-                        file = %dest_path.file_name().unwrap().to_string_lossy(),
-                        "removed orphan TOML"
-                    );
+                let source_skill_path = self.skill_path(skill_name).join("SKILL.md");
+
+                let excluded = || !self.skill_targets(skill_name).allows(dest.tool.as_deref());
+                if !source_skill_path.exists() || excluded() {
+                    orphans.push(Orphan {
+                        skill: skill_name.to_string(),
+                        display_name: dest_path.file_name().unwrap().to_string_lossy().into(),
+                        path: dest_path.clone(),
+                    });
                 }
             }
         }
@@ -266,24 +853,461 @@ impl SkillSync {
         Ok(())
     }
 
-    /// Handle a file system event
-    pub fn handle_event(&self, path: &Path) -> Result<()> {
-        // Only process events under our source directory
-        if !path.starts_with(&self.source) {
+    /// Skill names currently mirrored in any destination
+    fn known_skills(&self) -> BTreeSet<String> {
+        self.destinations.iter().flat_map(dest_skills).collect()
+    }
+
+    /// Check the deletion guard, holding `skills` back if it trips
+    fn deletion_allowed(&self, skills: &BTreeSet<String>) -> bool {
+        let Some(guard) = &self.guard else {
+            return true;
+        };
+        if skills.is_empty() {
+            return true;
+        }
+
+        let mut window = self.deletion_window.lock().unwrap();
+        let known = || self.known_skills().len();
+        if guard.admit(&mut window, skills, known, Instant::now()) {
+            return true;
+        }
+        let window = window.as_ref().expect("admit opens a window");
+
+        let mut held = self.held_deletions.lock().unwrap();
+        held.extend(skills.iter().cloned());
+        error!(
+            deleting = skills.len(),
+            removed_recently = window.removed(),
+            known = window.known(),
+            skills = ?skills,
+            "MASS DELETION HELD: source lost too many skills at once. \
+             Deletions are paused until the skills reappear or you run `skillsync confirm-deletions`."
+        );
+        false
+    }
+
+    /// Skills whose removal is currently held back by the deletion guard
+    pub fn held_deletions(&self) -> Vec<String> {
+        self.held_deletions
+            .lock()
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Carry out held deletions after the user confirmed them
+    ///
+    /// Only the held skills are removed; other orphans wait for the next
+    /// pass and its deletion guard.
+    pub fn confirm_deletions(&self) -> Result<()> {
+        let _pass = Pass(&self.trash_snapshot);
+        let held: BTreeSet<String> = self.held_deletions.lock().unwrap().clone();
+        warn!(skills = held.len(), "held deletions confirmed, removing");
+        let orphans = self
+            .find_orphans()?
+            .into_iter()
+            .filter(|orphan| held.contains(&orphan.skill))
+            .collect();
+        self.discard_orphans(orphans)?;
+        self.held_deletions
+            .lock()
+            .unwrap()
+            .retain(|skill| !held.contains(skill));
+        *self.deletion_window.lock().unwrap() = None;
+        self.update_manifest();
+        self.update_status(|_| {});
+        Ok(())
+    }
+
+    /// Release the hold when skills come back, or apply it when confirmed
+    ///
+    /// Called periodically by the watcher loop.
+    pub fn poll_held_deletions(&self) -> Result<()> {
+        let Some(guard) = &self.guard else {
             return Ok(());
+        };
+        let released = {
+            let mut held = self.held_deletions.lock().unwrap();
+            if guard.confirm_file.exists() {
+                // A confirmation with nothing held is stale and must not approve a later hold
+                fs::remove_file(&guard.confirm_file)?;
+                if !held.is_empty() {
+                    drop(held);
+                    return self.confirm_deletions();
+                }
+            }
+            if held.is_empty() {
+                return Ok(());
+            }
+            held.retain(|skill| !self.skill_path(skill).exists());
+            held.is_empty()
+        };
+        if released {
+            info!("held skills reappeared in source, deletion hold released");
+            // The removals counted so far were not real
+            *self.deletion_window.lock().unwrap() = None;
+            self.update_status(|_| {});
         }
+        Ok(())
+    }
 
-        if path.exists() {
-            if path.is_dir() {
-                self.sync_directory(path)?;
+    /// Handle one debounced batch of file system events
+    ///
+    /// The batch is collapsed into the set of affected skills and each skill
+    /// is synced or removed once, however many of its files changed. Skills
+    /// that vanished are removed only if the deletion guard allows it.
+    pub fn handle_batch(&self, paths: &[PathBuf]) {
+        let _pass = Pass(&self.trash_snapshot);
+        let started = Instant::now();
+
+        // A missing source root is not a request to delete its skills
+        let missing = self.missing_sources();
+        let paths: Vec<PathBuf> = paths
+            .iter()
+            .filter(|path| !missing.iter().any(|root| path.starts_with(root)))
+            .cloned()
+            .collect();
+        if paths.is_empty() && !missing.is_empty() {
+            warn!(missing = ?missing, "source directory missing, ignoring batch");
+            return;
+        }
+        let paths = paths.as_slice();
+
+        let renamed_from: BTreeSet<String> = self
+            .detect_renames(paths)
+            .into_iter()
+            .filter_map(|(from, to)| match self.rename_skill(&from, &to) {
+                Ok(()) => Some(from),
+                Err(e) => {
+                    warn!(from = %from, to = %to, error = %e, "failed to rename skill");
+                    None
+                }
+            })
+            .collect();
+
+        let mut skills = self.touched_skills(paths);
+        // An event on the source root itself means anything may have changed
+        if paths.iter().any(|path| self.sources.contains(path)) {
+            skills.extend(self.source_skills());
+        }
+        skills.retain(|skill| !renamed_from.contains(skill));
+
+        let vanished: BTreeSet<String> = skills
+            .iter()
+            .filter(|skill| !self.skill_path(skill).exists())
+            .cloned()
+            .collect();
+        let hold = !self.deletion_allowed(&vanished);
+
+        let (mut synced, mut removed) = (0, 0);
+        let mut results = Vec::new();
+        for skill in &skills {
+            let result = if vanished.contains(skill) {
+                if hold {
+                    continue;
+                }
+                removed += 1;
+                self.remove_skill(skill)
             } else {
-                self.sync_file(path)?;
+                synced += 1;
+                self.sync_skill(skill)
+            };
+            if let Err(e) = &result {
+                warn!(skill = %skill, error = %e, "failed to handle event");
+            }
+            results.push((self.skill_path(skill), result));
+        }
+        let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+        self.update_manifest();
+
+        self.update_status(|status| {
+            for (path, result) in &results {
+                match result {
+                    Ok(()) => status.clear_error(path),
+                    Err(e) => status.record_error(path, e),
+                }
             }
+            if failed == 0 {
+                status.record_success("batch");
+            }
+        });
+
+        info!(
+            events = paths.len(),
+            skills = skills.len(),
+            synced,
+            removed,
+            renamed = renamed_from.len(),
+            failed,
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "batch processed"
+        );
+    }
+
+    /// Bring one skill (or top-level file) in every destination up to date
+    ///
+    /// Files deleted from the skill since the last sync are removed from the
+    /// destinations as well.
+    fn sync_skill(&self, skill: &str) -> Result<()> {
+        let path = self.skill_path(skill);
+        if path.is_dir() {
+            self.sync_directory(&path)?;
         } else {
-            // File was deleted
-            self.remove_file(path)?;
+            self.sync_file(&path)?;
+        }
+
+        let targets = self.skill_targets(skill);
+        let mut orphans = Vec::new();
+        for dest in &self.destinations {
+            if !targets.allows(dest.tool.as_deref()) {
+                let output = skill_output(dest, skill);
+                if output.exists() && self.owns(dest, skill) {
+                    orphans.push(Orphan {
+                        skill: skill.to_string(),
+                        display_name: output
+                            .file_name()
+                            .map(|n| n.to_string_lossy().into_owned())
+                            .unwrap_or_default(),
+                        path: output,
+                    });
+                }
+                continue;
+            }
+            match dest.dest_type {
+                DestinationType::ClaudeStyle => self.find_orphans_recursive_claude(
+                    dest,
+                    &dest.base_path.join(skill),
+                    &mut orphans,
+                )?,
+                DestinationType::GeminiToml => {
+                    let toml_path = dest.base_path.join(format!("{}.toml", skill));
+                    if toml_path.exists() && !path.join("SKILL.md").exists() {
+                        orphans.push(Orphan {
+                            skill: skill.to_string(),
+                            display_name: format!("{}.toml", skill),
+                            path: toml_path,
+                        });
+                    }
+                }
+            }
+        }
+        for orphan in orphans {
+            self.discard(&orphan.path)?;
+            info!(file = %orphan.display_name, "removed");
+        }
+        Ok(())
+    }
+
+    /// Remove every destination output of a skill that left the source
+    fn remove_skill(&self, skill: &str) -> Result<()> {
+        for dest in &self.destinations {
+            let dest_path = skill_output(dest, skill);
+            if dest_path.exists() && self.owns(dest, skill) {
+                self.discard(&dest_path)?;
+            }
+        }
+        self.skill_inodes.lock().unwrap().remove(skill);
+
+        info!(skill = %skill, "removed");
+        Ok(())
+    }
+
+    /// Top-level entries of all source directories, merged by name
+    fn source_skills(&self) -> BTreeSet<String> {
+        self.sources
+            .iter()
+            .filter_map(|root| fs::read_dir(root).ok())
+            .flat_map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|e| e.file_name().to_str().map(str::to_string))
+            })
+            .collect()
+    }
+
+    /// Skills with at least one event in `paths`
+    fn touched_skills(&self, paths: &[PathBuf]) -> BTreeSet<String> {
+        paths
+            .iter()
+            .filter_map(|path| self.relative(path).ok())
+            .filter(|relative| relative.components().next().is_some())
+            .map(skill_of)
+            .collect()
+    }
+
+    /// Pair skills that vanished in this batch with skills that appeared in it
+    ///
+    /// The debouncer only reports paths, not rename events, so a rename shows
+    /// up as one skill disappearing and another appearing. They are paired by
+    /// the directory inode recorded when the old skill was last synced, or
+    /// failing that by identical SKILL.md output in a destination.
+    fn detect_renames(&self, paths: &[PathBuf]) -> Vec<(String, String)> {
+        let known = self.known_skills();
+        let touched = self.touched_skills(paths);
+        let mut vanished: Vec<String> = touched
+            .iter()
+            .filter(|skill| known.contains(*skill) && !self.skill_path(skill).exists())
+            .cloned()
+            .collect();
+        let appeared = touched
+            .iter()
+            .filter(|skill| !known.contains(*skill) && self.skill_path(skill).is_dir());
+
+        let mut renames = Vec::new();
+        for new in appeared {
+            if vanished.is_empty() {
+                break;
+            }
+            let inode = fs::metadata(self.skill_path(new)).ok().map(|m| m.ino());
+            let by_inode = {
+                let inodes = self.skill_inodes.lock().unwrap();
+                vanished
+                    .iter()
+                    .position(|old| inode.is_some() && inodes.get(old).copied() == inode)
+            };
+            let matched = by_inode.or_else(|| {
+                vanished
+                    .iter()
+                    .position(|old| self.same_skill_output(old, new))
+            });
+            if let Some(i) = matched {
+                renames.push((vanished.remove(i), new.clone()));
+            }
         }
+        renames
+    }
 
+    /// Whether a destination holds output for `old` identical to what `new` renders to
+    fn same_skill_output(&self, old: &str, new: &str) -> bool {
+        let Ok(content) = fs::read_to_string(self.skill_path(new).join("SKILL.md")) else {
+            return false;
+        };
+        self.destinations.iter().any(|dest| {
+            let Ok(content) = self.templated(&content, dest, new) else {
+                return false;
+            };
+            let (existing, expected) = match dest.dest_type {
+                DestinationType::ClaudeStyle => (
+                    dest.base_path.join(old).join("SKILL.md"),
+                    self.rewrite_paths(render_skill_md(&content, dest), dest, new),
+                ),
+                DestinationType::GeminiToml => (
+                    dest.base_path.join(format!("{}.toml", old)),
+                    render_toml(&content, dest, &self.skill_path(new)),
+                ),
+            };
+            fs::read_to_string(existing).is_ok_and(|existing| existing == expected)
+        })
+    }
+
+    /// Rename a skill's output in every destination without removing it first
+    fn rename_skill(&self, from: &str, to: &str) -> Result<()> {
+        for dest in &self.destinations {
+            let (from_path, to_path) = match dest.dest_type {
+                DestinationType::ClaudeStyle => {
+                    (dest.base_path.join(from), dest.base_path.join(to))
+                }
+                DestinationType::GeminiToml => (
+                    dest.base_path.join(format!("{}.toml", from)),
+                    dest.base_path.join(format!("{}.toml", to)),
+                ),
+            };
+            let movable = from_path.exists() && !to_path.exists() && self.owns(dest, from);
+            if movable && self.may_write(dest, to) {
+                fs::rename(&from_path, &to_path).with_context(|| {
+                    format!(
+                        "Failed to rename {} to {}",
+                        from_path.display(),
+                        to_path.display()
+                    )
+                })?;
+            }
+        }
+
+        let mut inodes = self.skill_inodes.lock().unwrap();
+        if let Some(inode) = inodes.remove(from) {
+            inodes.insert(to.to_string(), inode);
+        }
+        info!(from = %from, to = %to, "renamed skill");
         Ok(())
     }
 }
+
+/// Skill a source-relative path belongs to (its first component)
+fn skill_of(relative: &Path) -> String {
+    relative
+        .components()
+        .next()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Variables a skill template sees when rendered for `dest`
+pub(crate) fn template_context(home: &Path, dest: &Destination, skill: &str) -> TemplateContext {
+    let tool = dest.tool.as_deref().unwrap_or_default();
+    TemplateContext {
+        tool: tool.to_string(),
+        tool_name: preflight::tool(tool)
+            .map(|spec| spec.name.to_string())
+            .unwrap_or_default(),
+        dest_path: dest.base_path.display().to_string(),
+        dest_type: dest.dest_type.as_str().to_string(),
+        home: home.display().to_string(),
+        skill: skill.to_string(),
+    }
+}
+
+/// Gemini CLI command TOML for the SKILL.md document in `skill_dir`
+pub(crate) fn render_toml(content: &str, dest: &Destination, skill_dir: &Path) -> String {
+    let (_, body) = parse_frontmatter(content);
+    let body = match &dest.inline {
+        Some(settings) => inline_references(&body, skill_dir, settings, dest.rewrite_paths),
+        None => body,
+    };
+    // Relative links mean nothing in a flattened prompt; point them at the source
+    let body = match dest.rewrite_paths {
+        true => PathRewrite::for_flattened(skill_dir).apply(&body),
+        false => body,
+    };
+    generate_toml(
+        tool_description(content, dest.tool.as_deref(), &dest.fields),
+        &body,
+    )
+}
+
+/// SKILL.md as a Claude-style destination receives it
+fn render_skill_md(content: &str, dest: &Destination) -> String {
+    adapt_skill_md(content, dest.tool.as_deref(), &dest.fields)
+}
+
+/// Where a skill lives in a destination
+fn skill_output(dest: &Destination, skill: &str) -> PathBuf {
+    match dest.dest_type {
+        DestinationType::ClaudeStyle => dest.base_path.join(skill),
+        DestinationType::GeminiToml => dest.base_path.join(format!("{}.toml", skill)),
+    }
+}
+
+/// Skill names present in one destination
+fn dest_skills(dest: &Destination) -> BTreeSet<String> {
+    let Ok(entries) = fs::read_dir(&dest.base_path) else {
+        return BTreeSet::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = match dest.dest_type {
+                DestinationType::ClaudeStyle => path.file_name().and_then(|n| n.to_str()),
+                DestinationType::GeminiToml => path
+                    .extension()
+                    .filter(|ext| *ext == "toml")
+                    .and_then(|_| path.file_stem())
+                    .and_then(|n| n.to_str()),
+            };
+            name.map(str::to_string)
+        })
+        .collect()
+}
@@ -1,8 +1,85 @@
 //! Content transformations for YAML frontmatter and TOML generation
 
+use anyhow::{bail, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use crate::preflight::TOOLS;
+
+/// Per-destination frontmatter mapping: key -> new key, or `""` to drop it,
+/// and optionally the only keys written at all
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct FieldMap {
+    /// When set, only these keys (as named in the source) are written
+    #[serde(default)]
+    pub keep: Option<Vec<String>>,
+    /// Keys written under another name; `""` drops the key
+    #[serde(flatten)]
+    pub rename: BTreeMap<String, String>,
+}
+
+impl FieldMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write only `keys`, before renaming
+    pub fn keep_only(mut self, keys: &[&str]) -> Self {
+        self.keep = Some(keys.iter().map(|key| key.to_string()).collect());
+        self
+    }
+}
+
+impl<const N: usize> From<[(String, String); N]> for FieldMap {
+    fn from(rename: [(String, String); N]) -> Self {
+        Self {
+            keep: None,
+            rename: rename.into(),
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct FrontmatterData {
     pub description: Option<String>,
+    /// Every top-level `key: value` pair, in source order (values unquoted);
+    /// a block list (`key:` followed by `- item` lines) reads as `[a, b]`
+    pub fields: Vec<(String, String)>,
+    /// Destinations the skill is limited to, from the `skillsync:` key
+    pub targets: SkillTargets,
+    /// `skillsync: { template: true }`: render the body with `render_template`
+    pub template: bool,
+}
+
+/// `skillsync: { only: [...], exclude: [...] }`: which tools get a skill
+///
+/// Entries are tool ids from `preflight::TOOLS` (`gemini`, `codex`, ...).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SkillTargets {
+    /// When set, only destinations of these tools receive the skill
+    pub only: Option<Vec<String>>,
+    /// Destinations of these tools never receive the skill
+    pub exclude: Vec<String>,
+}
+
+impl SkillTargets {
+    /// Whether a destination for `tool` receives the skill
+    pub fn allows(&self, tool: Option<&str>) -> bool {
+        let listed = |names: &[String]| tool.is_some_and(|tool| names.iter().any(|n| n == tool));
+        if listed(&self.exclude) {
+            return false;
+        }
+        self.only.as_deref().is_none_or(listed)
+    }
+
+    /// Every tool id named in `only` or `exclude`
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.only
+            .iter()
+            .flatten()
+            .chain(&self.exclude)
+            .map(String::as_str)
+    }
 }
 
 /// Parse YAML frontmatter and return extracted data + content without frontmatter
@@ -27,11 +104,30 @@ pub fn parse_frontmatter(content: &str) -> (FrontmatterData, String) {
         return (FrontmatterData::default(), content.to_string());
     };
 
-    // Parse frontmatter for description field
+    // Parse frontmatter for description field and top-level scalar fields
     let mut description = None;
-    for line in &lines[1..end] {
+    let mut fields = Vec::new();
+    let mut targets = SkillTargets::default();
+    let mut template = false;
+    for (i, line) in lines[1..end].iter().enumerate() {
         if let Some(desc) = line.strip_prefix("description:") {
-            description = Some(desc.trim().trim_matches('"').trim_matches('\'').to_string());
+            description = Some(unquote(desc));
+        }
+        if line.starts_with(|c: char| c.is_whitespace() || c == '#' || c == '-') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            if key.trim() == "skillsync" {
+                let block = lines[i + 2..end]
+                    .iter()
+                    .take_while(|l| l.is_empty() || l.starts_with(char::is_whitespace));
+                (targets, template) = parse_skillsync(value, block);
+            }
+            let value = match value.trim().is_empty() {
+                true => block_list(&lines[i + 2..end]).unwrap_or_default(),
+                false => unquote(value),
+            };
+            fields.push((key.trim().to_string(), value));
         }
     }
 
@@ -44,7 +140,541 @@ pub fn parse_frontmatter(content: &str) -> (FrontmatterData, String) {
 
     let stripped_content = lines[content_start..].join("\n");
 
-    (FrontmatterData { description }, stripped_content)
+    (
+        FrontmatterData {
+            description,
+            fields,
+            targets,
+            template,
+        },
+        stripped_content,
+    )
+}
+
+/// Read `only`/`exclude`/`template` from a flow mapping (`{ only: [a, b] }`)
+/// or an indented block below the `skillsync:` key, with inline or dash lists
+fn parse_skillsync<'a>(
+    inline: &str,
+    block: impl Iterator<Item = &'a &'a str>,
+) -> (SkillTargets, bool) {
+    let mut entries: Vec<(String, Vec<String>)> = Vec::new();
+    let inline = inline.trim();
+    if let Some(flow) = inline.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+        for entry in split_top_level(flow) {
+            if let Some((key, value)) = entry.split_once(':') {
+                entries.push((key.trim().to_string(), parse_list(value)));
+            }
+        }
+    } else {
+        for line in block {
+            let line = line.trim();
+            if let Some(item) = line.strip_prefix('-') {
+                if let Some((_, items)) = entries.last_mut() {
+                    items.push(unquote(item));
+                }
+            } else if let Some((key, value)) = line.split_once(':') {
+                entries.push((key.trim().to_string(), parse_list(value)));
+            }
+        }
+    }
+
+    let mut targets = SkillTargets::default();
+    let mut template = false;
+    for (key, items) in entries {
+        match key.as_str() {
+            "only" => targets.only = Some(items),
+            "exclude" => targets.exclude = items,
+            "template" => template = items.iter().any(|item| item == "true"),
+            _ => {}
+        }
+    }
+    (targets, template)
+}
+
+/// `[a, "b"]` or a single scalar as a list; empty for no value
+fn parse_list(value: &str) -> Vec<String> {
+    let value = value.trim();
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .unwrap_or(value);
+    inner
+        .split(',')
+        .map(unquote)
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// `[a, b]` for a block of `- item` lines; `None` for an empty block or a
+/// nested mapping
+fn block_list(following: &[&str]) -> Option<String> {
+    let items: Vec<String> = following
+        .iter()
+        .take_while(|line| line.starts_with(|c: char| c.is_whitespace() || c == '-'))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.trim().strip_prefix('-').map(unquote))
+        .collect::<Option<_>>()?;
+    (!items.is_empty()).then(|| format!("[{}]", items.join(", ")))
+}
+
+/// Split on commas that are not inside `[...]` or quotes
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    let mut quote = None;
+    for (i, c) in text.char_indices() {
+        match c {
+            '"' | '\'' if quote == Some(c) => quote = None,
+            '"' | '\'' if quote.is_none() => quote = Some(c),
+            _ if quote.is_some() => {}
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+fn unquote(value: &str) -> String {
+    value
+        .trim()
+        .trim_matches('"')
+        .trim_matches('\'')
+        .to_string()
+}
+
+/// Frontmatter as ordered top-level entries, so it can be edited and written back
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frontmatter {
+    entries: Vec<Entry>,
+}
+
+/// A top-level key with every line belonging to it (nested blocks, list
+/// items, comments), kept verbatim
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    key: String,
+    lines: Vec<String>,
+}
+
+impl Frontmatter {
+    fn parse<'a>(lines: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut entries: Vec<Entry> = Vec::new();
+        for line in lines {
+            let key = line
+                .split_once(':')
+                .filter(|_| !line.starts_with(|c: char| c.is_whitespace() || c == '#' || c == '-'))
+                .map(|(key, _)| key.trim().to_string());
+            match (key, entries.last_mut()) {
+                (None, Some(entry)) => entry.lines.push(line.to_string()),
+                (key, _) => entries.push(Entry {
+                    key: key.unwrap_or_default(),
+                    lines: vec![line.to_string()],
+                }),
+            }
+        }
+        Self { entries }
+    }
+
+    /// Unquoted scalar value of a top-level key
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.entries
+            .iter()
+            .find(|entry| entry.key == key)
+            .and_then(|entry| entry.lines[0].split_once(':'))
+            .map(|(_, value)| unquote(value))
+    }
+
+    /// Apply the override section of `tool` (e.g. `gemini:`) to the base
+    /// keys and remove every tool section and the `skillsync:` key, which
+    /// only skillsync reads, from the result
+    pub fn for_tool(mut self, tool: Option<&str>) -> Self {
+        let is_section = |entry: &Entry| {
+            entry.key == "skillsync" || TOOLS.iter().any(|spec| spec.id == entry.key)
+        };
+        let overrides = self
+            .entries
+            .iter()
+            .find(|entry| tool.is_some_and(|tool| entry.key == tool))
+            .map(section_entries)
+            .unwrap_or_default();
+        self.entries.retain(|entry| !is_section(entry));
+
+        for entry in overrides {
+            match self.entries.iter_mut().find(|e| e.key == entry.key) {
+                Some(existing) => *existing = entry,
+                None => self.entries.push(entry),
+            }
+        }
+        self
+    }
+
+    /// Keep, rename or drop top-level keys according to `fields`; lines
+    /// before the first key stay
+    pub fn map_fields(mut self, fields: &FieldMap) -> Self {
+        if let Some(keep) = &fields.keep {
+            self.entries
+                .retain(|entry| entry.key.is_empty() || keep.contains(&entry.key));
+        }
+        self.entries
+            .retain_mut(|entry| match fields.rename.get(&entry.key) {
+                None => true,
+                Some(target) if target.is_empty() => false,
+                Some(target) => {
+                    let colon = entry.lines[0].find(':').unwrap_or(0);
+                    entry.lines[0] = format!("{}{}", target, &entry.lines[0][colon..]);
+                    entry.key = target.clone();
+                    true
+                }
+            });
+        self
+    }
+
+    fn render(&self) -> String {
+        self.entries
+            .iter()
+            .flat_map(|entry| &entry.lines)
+            .fold(String::new(), |mut out, line| {
+                out.push_str(line);
+                out.push('\n');
+                out
+            })
+    }
+}
+
+/// Children of a tool section, from an indented block or a flow mapping
+fn section_entries(section: &Entry) -> Vec<Entry> {
+    let inline = section.lines[0]
+        .split_once(':')
+        .map(|(_, value)| value.trim())
+        .unwrap_or("");
+    if let Some(flow) = inline.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+        return split_top_level(flow)
+            .into_iter()
+            .filter_map(|pair| pair.split_once(':'))
+            .map(|(key, value)| Entry {
+                key: key.trim().to_string(),
+                lines: vec![format!("{}: {}", key.trim(), value.trim())],
+            })
+            .collect();
+    }
+
+    let block = &section.lines[1..];
+    let indent = block
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    Frontmatter::parse(block.iter().map(|line| line.get(indent..).unwrap_or("")))
+        .entries
+        .into_iter()
+        .filter(|entry| !entry.key.is_empty())
+        .collect()
+}
+
+/// Frontmatter lines and everything after the closing `---` line, verbatim
+fn split_frontmatter(content: &str) -> Option<(Vec<&str>, &str)> {
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        offset += line.len();
+        let line = line.trim_end_matches(['\n', '\r']);
+        if line == "---" {
+            return Some((lines, &rest[offset..]));
+        }
+        lines.push(line);
+    }
+    None
+}
+
+/// Frontmatter of `content` as `tool` should see it
+fn adapted_frontmatter(
+    content: &str,
+    tool: Option<&str>,
+    fields: &FieldMap,
+) -> Option<Frontmatter> {
+    let (lines, _) = split_frontmatter(content)?;
+    Some(Frontmatter::parse(lines).for_tool(tool).map_fields(fields))
+}
+
+/// SKILL.md for a Claude-style destination of `tool`
+///
+/// Only the frontmatter is rewritten, and only when an override section, the
+/// `skillsync:` key or a field mapping changes it; otherwise the content is
+/// returned unchanged.
+pub fn adapt_skill_md(content: &str, tool: Option<&str>, fields: &FieldMap) -> String {
+    let Some((lines, body)) = split_frontmatter(content) else {
+        return content.to_string();
+    };
+    let original = Frontmatter::parse(lines);
+    let adapted = original.clone().for_tool(tool).map_fields(fields);
+    if adapted == original {
+        return content.to_string();
+    }
+    format!("---\n{}---\n{}", adapted.render(), body)
+}
+
+/// The `description` `tool` sees, after its override section and `fields`
+pub fn tool_description(content: &str, tool: Option<&str>, fields: &FieldMap) -> Option<String> {
+    adapted_frontmatter(content, tool, fields)
+        .and_then(|frontmatter| frontmatter.get("description"))
+}
+
+/// Relative link targets in Markdown `[text](target)` links
+pub fn relative_links(text: &str) -> Vec<String> {
+    let mut targets = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("](") {
+        rest = &rest[start + 2..];
+        // `<...>` destinations may contain spaces and parentheses
+        let (raw, end) = match rest.strip_prefix('<') {
+            Some(inner) => match inner.find(['>', '\n']) {
+                Some(close) if inner[close..].starts_with('>') => (&inner[..close], close + 2),
+                _ => continue,
+            },
+            None => {
+                let Some(end) = rest.find(')') else {
+                    break;
+                };
+                (rest[..end].split_whitespace().next().unwrap_or(""), end)
+            }
+        };
+        let target = raw.split('#').next().unwrap_or("");
+        let external = target.contains("://") || target.starts_with("mailto:");
+        if !target.is_empty() && !external && !target.starts_with('/') {
+            targets.push(target.to_string());
+        }
+        rest = &rest[end..];
+    }
+    targets
+}
+
+/// Values a skill template can refer to, for one destination
+#[derive(Debug, Clone, Default)]
+pub struct TemplateContext {
+    /// Tool id, e.g. `gemini`; empty for destinations without a tool
+    pub tool: String,
+    /// Display name of the tool, e.g. `Gemini CLI`
+    pub tool_name: String,
+    pub dest_path: String,
+    /// `claude-style` or `gemini-toml`
+    pub dest_type: String,
+    pub home: String,
+    pub skill: String,
+}
+
+impl TemplateContext {
+    /// Variable names accepted in `{{ }}` and conditions
+    pub const VARIABLES: &'static [&'static str] = &[
+        "tool",
+        "tool.name",
+        "dest.path",
+        "dest.type",
+        "home",
+        "skill",
+    ];
+
+    fn get(&self, name: &str) -> Option<&str> {
+        let value = match name {
+            "tool" => &self.tool,
+            "tool.name" => &self.tool_name,
+            "dest.path" => &self.dest_path,
+            "dest.type" => &self.dest_type,
+            "home" => &self.home,
+            "skill" => &self.skill,
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+/// One open `{% if %}` block
+struct Branch {
+    /// Line of the `{% if %}`, for unterminated-block errors
+    line: usize,
+    /// Whether the enclosing block renders at all
+    parent_active: bool,
+    /// Whether this branch renders
+    active: bool,
+    /// Whether an earlier branch of this block already rendered
+    taken: bool,
+    seen_else: bool,
+}
+
+/// Render `{{ var }}` substitutions and `{% if %}`/`{% elif %}`/`{% else %}`/
+/// `{% endif %}` blocks
+///
+/// Only the variables in `TemplateContext::VARIABLES` and quoted string
+/// literals are available; there are no loops, includes or filters. A tag
+/// alone on its line takes the line with it. `first_line` is the line number
+/// of `text` in its file, used in error messages.
+pub fn render_template(text: &str, ctx: &TemplateContext, first_line: usize) -> Result<String> {
+    let mut out = String::new();
+    let mut branches: Vec<Branch> = Vec::new();
+    let mut line = first_line;
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{").into_iter().chain(rest.find("{%")).min() {
+        let active = branches.last().is_none_or(|b| b.active);
+        let mut before = &rest[..start];
+        let is_tag = rest[start..].starts_with("{%");
+        let close = if is_tag { "%}" } else { "}}" };
+        let Some(len) = rest[start + 2..].find(close) else {
+            bail!(
+                "line {}: unclosed `{}`",
+                line + before.matches('\n').count(),
+                &rest[start..start + 2]
+            );
+        };
+        let inner = rest[start + 2..start + 2 + len].trim();
+        let mut after = &rest[start + 2 + len + 2..];
+
+        // A tag alone on its line removes the whole line
+        let mut swallowed_newline = false;
+        if is_tag {
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+            let line_end = after.find('\n');
+            let lead = &before[line_start..];
+            let trail = &after[..line_end.unwrap_or(after.len())];
+            let standalone = lead.trim().is_empty()
+                && trail.trim().is_empty()
+                && (line_start > 0 || out.is_empty() || out.ends_with('\n'));
+            if standalone {
+                before = &before[..line_start];
+                after = line_end.map_or("", |end| &after[end + 1..]);
+                swallowed_newline = line_end.is_some();
+            }
+        }
+
+        if active {
+            out.push_str(before);
+        }
+        line += before.matches('\n').count() + inner.matches('\n').count();
+
+        if !is_tag {
+            let value = eval_value(inner, ctx, line)?;
+            if active {
+                out.push_str(&value);
+            }
+        } else {
+            let (keyword, condition) = inner.split_once(char::is_whitespace).unwrap_or((inner, ""));
+            match keyword {
+                "if" => {
+                    let matched = eval_condition(condition, ctx, line)?;
+                    branches.push(Branch {
+                        line,
+                        parent_active: active,
+                        active: active && matched,
+                        taken: matched,
+                        seen_else: false,
+                    });
+                }
+                "elif" | "else" => {
+                    let matched = match keyword {
+                        "elif" => eval_condition(condition, ctx, line)?,
+                        _ if condition.trim().is_empty() => true,
+                        _ => bail!("line {}: `else` takes no condition", line),
+                    };
+                    let Some(branch) = branches.last_mut().filter(|b| !b.seen_else) else {
+                        bail!("line {}: `{}` without a matching `if`", line, keyword);
+                    };
+                    branch.active = branch.parent_active && !branch.taken && matched;
+                    branch.taken |= matched;
+                    branch.seen_else = keyword == "else";
+                }
+                "endif" => {
+                    if branches.pop().is_none() {
+                        bail!("line {}: `endif` without a matching `if`", line);
+                    }
+                }
+                other => bail!("line {}: unknown template tag `{}`", line, other),
+            }
+        }
+        line += usize::from(swallowed_newline);
+        rest = after;
+    }
+
+    if let Some(branch) = branches.last() {
+        bail!("line {}: `if` is never closed with `endif`", branch.line);
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// A variable or a quoted string literal
+fn eval_value(expr: &str, ctx: &TemplateContext, line: usize) -> Result<String> {
+    let expr = expr.trim();
+    for quote in ['"', '\''] {
+        if let Some(literal) = expr.strip_prefix(quote).and_then(|s| s.strip_suffix(quote)) {
+            return Ok(literal.to_string());
+        }
+    }
+    match ctx.get(expr) {
+        Some(value) => Ok(value.to_string()),
+        None => bail!(
+            "line {}: unknown template variable `{}` (known: {})",
+            line,
+            expr,
+            TemplateContext::VARIABLES.join(", ")
+        ),
+    }
+}
+
+/// `a == b`, `a != b`, or `a in ["x", "y"]`
+fn eval_condition(condition: &str, ctx: &TemplateContext, line: usize) -> Result<bool> {
+    if let Some((left, right)) = condition.split_once("==") {
+        return Ok(eval_value(left, ctx, line)? == eval_value(right, ctx, line)?);
+    }
+    if let Some((left, right)) = condition.split_once("!=") {
+        return Ok(eval_value(left, ctx, line)? != eval_value(right, ctx, line)?);
+    }
+    if let Some((left, right)) = condition.split_once(" in ") {
+        let value = eval_value(left, ctx, line)?;
+        let right = right.trim();
+        let Some(list) = right.strip_prefix('[').and_then(|s| s.strip_suffix(']')) else {
+            bail!(
+                "line {}: expected a list after `in`, found `{}`",
+                line,
+                right
+            );
+        };
+        for item in split_top_level(list) {
+            if eval_value(item, ctx, line)? == value {
+                return Ok(true);
+            }
+        }
+        return Ok(false);
+    }
+    bail!(
+        "line {}: unsupported condition `{}` (use ==, != or in [...])",
+        line,
+        condition.trim()
+    )
+}
+
+/// Apply `render_template` to the body of a SKILL.md that opted in with
+/// `skillsync: { template: true }`; frontmatter is left as written
+pub fn apply_template(content: &str, ctx: &TemplateContext) -> Result<String> {
+    let (frontmatter, _) = parse_frontmatter(content);
+    if !frontmatter.template {
+        return Ok(content.to_string());
+    }
+    let Some((lines, body)) = split_frontmatter(content) else {
+        return Ok(content.to_string());
+    };
+    let head = &content[..content.len() - body.len()];
+    // The body starts after both `---` lines
+    let rendered = render_template(body, ctx, lines.len() + 3)?;
+    Ok(format!("{}{}", head, rendered))
 }
 
 /// Generate TOML format for Gemini CLI
@@ -93,6 +723,170 @@ mod tests {
         assert_eq!(stripped, "Content");
     }
 
+    #[test]
+    fn test_parse_frontmatter_collects_top_level_fields() {
+        let content = "---\nglobs: \"*.rs\"\nalwaysApply: false\ntools:\n  - a\n  - 'b'\ncursor:\n  model: fast\n---\nBody";
+        let (frontmatter, _) = parse_frontmatter(content);
+
+        assert_eq!(
+            frontmatter.fields,
+            vec![
+                ("globs".to_string(), "*.rs".to_string()),
+                ("alwaysApply".to_string(), "false".to_string()),
+                ("tools".to_string(), "[a, b]".to_string()),
+                ("cursor".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_frontmatter_targets() {
+        let flow =
+            "---\nname: a\nskillsync: { only: [codex, \"gemini\"], exclude: [cursor] }\n---\nBody";
+        let (frontmatter, _) = parse_frontmatter(flow);
+        assert_eq!(
+            frontmatter.targets,
+            SkillTargets {
+                only: Some(vec!["codex".to_string(), "gemini".to_string()]),
+                exclude: vec!["cursor".to_string()],
+            }
+        );
+
+        let block = "---\nskillsync:\n  exclude:\n    - cursor\n    - codex\nname: b\n---\nBody";
+        let (frontmatter, _) = parse_frontmatter(block);
+        assert_eq!(frontmatter.targets.only, None);
+        assert_eq!(frontmatter.targets.exclude, vec!["cursor", "codex"]);
+        assert!(frontmatter.targets.allows(Some("gemini")));
+        assert!(!frontmatter.targets.allows(Some("codex")));
+        assert!(frontmatter.targets.allows(None));
+    }
+
+    #[test]
+    fn test_adapt_skill_md_applies_tool_section_and_fields() {
+        let content = "---\nname: a\ndescription: Base\nallowed-tools: Read\ncursor:\n  description: \"For Cursor\"\n  model: fast\ngemini: { description: For Gemini }\n---\n\nBody\n";
+        let fields = FieldMap::from([
+            ("allowed-tools".to_string(), String::new()),
+            ("model".to_string(), "model-hint".to_string()),
+        ]);
+
+        assert_eq!(
+            adapt_skill_md(content, Some("cursor"), &fields),
+            "---\nname: a\ndescription: \"For Cursor\"\nmodel-hint: fast\n---\n\nBody\n"
+        );
+        assert_eq!(
+            tool_description(content, Some("gemini"), &FieldMap::new()),
+            Some("For Gemini".to_string())
+        );
+
+        let targeted =
+            "---\nname: a\nskillsync:\n  only: [codex]\ncodex: { description: C }\n---\nBody";
+        assert_eq!(
+            adapt_skill_md(targeted, Some("codex"), &FieldMap::new()),
+            "---\nname: a\ndescription: C\n---\nBody"
+        );
+
+        let plain = "---\nname: a\n---\nBody";
+        assert_eq!(
+            adapt_skill_md(plain, Some("codex"), &FieldMap::new()),
+            plain
+        );
+    }
+
+    #[test]
+    fn test_field_map_keeps_body_verbatim() {
+        let content = "---\n# synced\nname: a\nmodel: opus\nallowed-tools:\n  - Bash\ndescription: D\n---\nBody\n---\nmore";
+        let strip = FieldMap::from([
+            ("allowed-tools".to_string(), String::new()),
+            ("model".to_string(), String::new()),
+        ]);
+        assert_eq!(
+            adapt_skill_md(content, None, &strip),
+            "---\n# synced\nname: a\ndescription: D\n---\nBody\n---\nmore"
+        );
+
+        // `keep` names keys as the source spells them, before renaming
+        let keep = FieldMap::from([("description".to_string(), "summary".to_string())])
+            .keep_only(&["name", "description"]);
+        assert_eq!(
+            adapt_skill_md(content, None, &keep),
+            "---\n# synced\nname: a\nsummary: D\n---\nBody\n---\nmore"
+        );
+    }
+
+    fn gemini() -> TemplateContext {
+        TemplateContext {
+            tool: "gemini".to_string(),
+            tool_name: "Gemini CLI".to_string(),
+            skill: "deploy".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_render_template_variables_and_blocks() {
+        let text = "Run {{ skill }} in {{tool.name}}.\n\
+                    {% if tool == \"gemini\" %}\n\
+                    Use /deploy.\n\
+                    {% elif tool in [\"codex\", \"cursor\"] %}\n\
+                    Ask for deploy.\n\
+                    {% else %}\n\
+                    Invoke the skill.\n\
+                    {% endif %}\n\
+                    Literal {{ \"{{\" }}.\n";
+        assert_eq!(
+            render_template(text, &gemini(), 1).unwrap(),
+            "Run deploy in Gemini CLI.\nUse /deploy.\nLiteral {{.\n"
+        );
+
+        let codex = TemplateContext {
+            tool: "codex".to_string(),
+            ..gemini()
+        };
+        assert!(render_template(text, &codex, 1)
+            .unwrap()
+            .contains("\nAsk for deploy.\nLiteral"));
+    }
+
+    #[test]
+    fn test_render_template_errors_name_the_line() {
+        let err = render_template("a\nb {{ tool.version }}\n", &gemini(), 10).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("line 11: unknown template variable `tool.version`"));
+
+        let err = render_template("x\n{% if tool == \"a\" %}\ny\n", &gemini(), 1).unwrap_err();
+        assert_eq!(err.to_string(), "line 2: `if` is never closed with `endif`");
+
+        let err = render_template("{% for x in y %}", &gemini(), 1).unwrap_err();
+        assert_eq!(err.to_string(), "line 1: unknown template tag `for`");
+    }
+
+    #[test]
+    fn test_apply_template_requires_opt_in() {
+        let plain = "---\nname: a\n---\n{{ unknown }}\n";
+        assert_eq!(apply_template(plain, &gemini()).unwrap(), plain);
+
+        let opted =
+            "---\nname: a\nskillsync: { template: true }\n---\n\nFor {{ tool }}\n{{ bad }}\n";
+        assert_eq!(
+            apply_template(opted, &gemini()).unwrap_err().to_string(),
+            "line 7: unknown template variable `bad` (known: tool, tool.name, dest.path, dest.type, home, skill)"
+        );
+    }
+
+    #[test]
+    fn test_relative_links_skip_external_and_anchors() {
+        let text = "See [ref](references/api.md#auth), [site](https://x.dev), \
+                    [top](#usage) and [img](<assets/a b.png> \"title\").";
+        assert_eq!(
+            relative_links(text),
+            vec![
+                "references/api.md".to_string(),
+                "assets/a b.png".to_string()
+            ]
+        );
+    }
+
     #[test]
     fn test_generate_toml_with_description() {
         let toml = generate_toml(Some("My skill".to_string()), "Test content");
@@ -0,0 +1,428 @@
+//! Dated trash for removed destination content
+//!
+//! Instead of deleting destination files, SkillSync moves them into
+//! `<state>/trash/<snapshot>/` where `<snapshot>` is the UTC time of removal.
+//! Each snapshot mirrors the original absolute paths and lists them in an
+//! `.entries` file, so content can be put back with `skillsync restore`.
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::{info, warn};
+
+use crate::clock::utc_timestamp;
+use crate::destination::{Destination, DestinationType};
+
+const ENTRIES_FILE: &str = ".entries";
+
+#[derive(Debug, Clone)]
+pub struct Trash {
+    root: PathBuf,
+    max_snapshots: usize,
+    max_age: Duration,
+}
+
+/// A single dated trash directory
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub id: String,
+    pub path: PathBuf,
+    /// Original locations of everything moved into this snapshot
+    pub entries: Vec<PathBuf>,
+}
+
+/// A source and the destinations synced from it, e.g. the home skills or
+/// one project's `.claude/skills`
+#[derive(Debug, Clone)]
+pub struct Mirror {
+    pub source: PathBuf,
+    pub destinations: Vec<Destination>,
+}
+
+/// What a restore put back
+#[derive(Debug, Clone)]
+pub struct Restored {
+    pub snapshot: String,
+    pub paths: Vec<PathBuf>,
+}
+
+impl Restored {
+    /// One line per restored path and a summary
+    pub fn report(&self) -> String {
+        let mut report = String::new();
+        for path in &self.paths {
+            report.push_str(&format!("restored {}\n", path.display()));
+        }
+        report.push_str(&format!(
+            "Restored {} item(s) from {}\n",
+            self.paths.len(),
+            self.snapshot
+        ));
+        report
+    }
+}
+
+impl Trash {
+    pub fn new(root: PathBuf, max_snapshots: usize, max_age: Duration) -> Self {
+        Self {
+            root,
+            max_snapshots,
+            max_age,
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Id for a new snapshot, from the current time
+    pub fn snapshot_id(&self) -> String {
+        timestamp_id(SystemTime::now())
+    }
+
+    /// Move `path` into the snapshot `id`
+    ///
+    /// Callers use one id for everything a sync pass removes, so the pass can
+    /// be restored as a whole.
+    pub fn discard(&self, path: &Path, id: &str) -> Result<PathBuf> {
+        let snapshot_dir = self.root.join(id);
+        let mut target = snapshot_dir.join(strip_root(path));
+
+        // The same path can be discarded twice into one snapshot
+        let mut n = 1;
+        while target.exists() {
+            let mut name = target.file_name().unwrap_or_default().to_os_string();
+            name.push(format!(".{}", n));
+            target.set_file_name(name);
+            n += 1;
+        }
+
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        move_path(path, &target)?;
+
+        let mut entries = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(snapshot_dir.join(ENTRIES_FILE))?;
+        writeln!(entries, "{}\t{}", path.display(), target.display())?;
+
+        Ok(target)
+    }
+
+    /// All snapshots, oldest first
+    pub fn snapshots(&self) -> Result<Vec<Snapshot>> {
+        if !self.root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut snapshots = Vec::new();
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let Some(id) = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .map(str::to_string)
+            else {
+                continue;
+            };
+            let entries = read_entries(&path)?
+                .into_iter()
+                .map(|(original, _)| original)
+                .collect();
+            snapshots.push(Snapshot { id, path, entries });
+        }
+        snapshots.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(snapshots)
+    }
+
+    /// Find a snapshot by id; `latest` selects the newest one
+    pub fn snapshot(&self, id: &str) -> Result<Snapshot> {
+        let mut snapshots = self.snapshots()?;
+        let found = if id == "latest" {
+            snapshots.pop()
+        } else {
+            snapshots.into_iter().find(|s| s.id == id)
+        };
+        found.with_context(|| format!("No trash snapshot '{}'", id))
+    }
+
+    /// Put trashed content back where it came from
+    ///
+    /// Each entry belongs to the mirror whose destination holds it; with
+    /// `skill`, only entries of that skill are restored. Restored skills that
+    /// no longer exist in their mirror's source are copied there too,
+    /// otherwise the daemon would treat them as orphans and remove them
+    /// again. A Claude-style copy is taken as is; a skill that only comes
+    /// back as a Gemini command is rebuilt from its description and prompt.
+    pub fn restore(
+        &self,
+        snapshot: &Snapshot,
+        skill: Option<&str>,
+        mirrors: &[Mirror],
+    ) -> Result<Vec<PathBuf>> {
+        let mut restored = Vec::new();
+        let mut remaining = Vec::new();
+        let mut missing_from_source = HashSet::new();
+        let mut commands = Vec::new();
+
+        for (original, trashed) in read_entries(&snapshot.path)? {
+            let owner = owner_of(mirrors, &original);
+            // Skills replaced by `skillsync import --force` were trashed from a source
+            let skill_name = owner
+                .and_then(|(_, dest)| skill_name_in(dest, &original))
+                .or_else(|| source_skill(mirrors, &original));
+
+            if skill.is_some() && skill_name.as_deref() != skill {
+                remaining.push((original, trashed));
+                continue;
+            }
+            if !trashed.exists() {
+                continue;
+            }
+            if original.exists() {
+                warn!(path = %original.display(), "not restoring, path exists");
+                remaining.push((original, trashed));
+                continue;
+            }
+
+            if let Some(parent) = original.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            move_path(&trashed, &original)?;
+            info!(path = %original.display(), snapshot = %snapshot.id, "restored from trash");
+
+            if let (Some((mirror, dest)), Some(name)) = (owner, skill_name) {
+                let source = &mirror.source;
+                match dest.dest_type {
+                    DestinationType::ClaudeStyle => {
+                        if !source.join(&name).exists() {
+                            missing_from_source.insert((source.clone(), name.clone()));
+                        }
+                        let source_path = source.join(original.strip_prefix(&dest.base_path)?);
+                        if missing_from_source.contains(&(source.clone(), name))
+                            && !source_path.exists()
+                        {
+                            copy_recursive(&original, &source_path)?;
+                        }
+                    }
+                    DestinationType::GeminiToml => {
+                        commands.push((source.join(&name), name, original.clone()))
+                    }
+                }
+            }
+            restored.push(original);
+        }
+
+        // Only once every Claude-style copy had the chance to seed the source
+        for (skill_dir, name, command) in commands {
+            if skill_dir.exists() {
+                continue;
+            }
+            let text = fs::read_to_string(&command)
+                .with_context(|| format!("Failed to read {}", command.display()))?;
+            let skill_md = skill_md_from_command(&name, &text)
+                .with_context(|| format!("Failed to rebuild skill from {}", command.display()))?;
+            fs::create_dir_all(&skill_dir)?;
+            fs::write(skill_dir.join("SKILL.md"), skill_md)?;
+            info!(skill = %name, path = %skill_dir.display(), "rebuilt source skill from restored command");
+        }
+
+        if let (Some(skill), true) = (skill, restored.is_empty()) {
+            bail!(
+                "Nothing to restore for skill '{}' in snapshot '{}'",
+                skill,
+                snapshot.id
+            );
+        }
+
+        if remaining.is_empty() {
+            fs::remove_dir_all(&snapshot.path)?;
+        } else {
+            write_entries(&snapshot.path, &remaining)?;
+        }
+
+        Ok(restored)
+    }
+
+    /// Restore from snapshot `id`, see [`Trash::restore`]
+    pub fn restore_snapshot(
+        &self,
+        id: &str,
+        skill: Option<&str>,
+        mirrors: &[Mirror],
+    ) -> Result<Restored> {
+        let snapshot = self.snapshot(id)?;
+        let paths = self.restore(&snapshot, skill, mirrors)?;
+        Ok(Restored {
+            snapshot: snapshot.id,
+            paths,
+        })
+    }
+
+    /// Drop snapshots beyond the retention limits, returning how many were removed
+    pub fn prune(&self) -> Result<usize> {
+        let snapshots = self.snapshots()?;
+        let now = SystemTime::now();
+        let excess = snapshots.len().saturating_sub(self.max_snapshots);
+
+        let mut removed = 0;
+        for (i, snapshot) in snapshots.iter().enumerate() {
+            let expired = fs::metadata(&snapshot.path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age > self.max_age);
+
+            if i < excess || expired {
+                fs::remove_dir_all(&snapshot.path)
+                    .with_context(|| format!("Failed to prune {}", snapshot.path.display()))?;
+                info!(snapshot = %snapshot.id, "pruned trash snapshot");
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+}
+
+/// Mirror and destination holding `path`; the innermost destination wins
+fn owner_of<'a>(mirrors: &'a [Mirror], path: &Path) -> Option<(&'a Mirror, &'a Destination)> {
+    mirrors
+        .iter()
+        .flat_map(|mirror| mirror.destinations.iter().map(move |dest| (mirror, dest)))
+        .filter(|(_, dest)| path.starts_with(&dest.base_path))
+        .max_by_key(|(_, dest)| dest.base_path.components().count())
+}
+
+/// Skill directory of a mirror's source that `path` is or lies in
+fn source_skill(mirrors: &[Mirror], path: &Path) -> Option<String> {
+    mirrors.iter().find_map(|mirror| {
+        let relative = path.strip_prefix(&mirror.source).ok()?;
+        Some(
+            relative
+                .components()
+                .next()?
+                .as_os_str()
+                .to_str()?
+                .to_string(),
+        )
+    })
+}
+
+/// Skill that a destination path belongs to, e.g. `foo` for `<base>/foo/SKILL.md`
+pub(crate) fn skill_name_in(dest: &Destination, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(&dest.base_path).ok()?;
+    let first = relative.components().next()?.as_os_str().to_str()?;
+    match dest.dest_type {
+        DestinationType::ClaudeStyle => Some(first.to_string()),
+        DestinationType::GeminiToml => first.strip_suffix(".toml").map(str::to_string),
+    }
+}
+
+/// SKILL.md for a Gemini command, from its `description` and `prompt`
+fn skill_md_from_command(name: &str, text: &str) -> Result<String> {
+    let command: toml::Table = toml::from_str(text)?;
+    let field = |key: &str| command.get(key).and_then(toml::Value::as_str);
+    let prompt = field("prompt").context("command has no prompt")?;
+    let description = field("description").unwrap_or("Custom skill");
+    Ok(format!(
+        "---\nname: {}\ndescription: \"{}\"\n---\n\n{}\n",
+        name,
+        description.replace('\\', "\\\\").replace('"', "\\\""),
+        prompt.trim()
+    ))
+}
+
+fn read_entries(snapshot_dir: &Path) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let entries_file = snapshot_dir.join(ENTRIES_FILE);
+    if !entries_file.exists() {
+        return Ok(Vec::new());
+    }
+    let text = fs::read_to_string(&entries_file)?;
+    Ok(text
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(original, trashed)| (PathBuf::from(original), PathBuf::from(trashed)))
+        .collect())
+}
+
+fn write_entries(snapshot_dir: &Path, entries: &[(PathBuf, PathBuf)]) -> Result<()> {
+    let text: String = entries
+        .iter()
+        .map(|(original, trashed)| format!("{}\t{}\n", original.display(), trashed.display()))
+        .collect();
+    fs::write(snapshot_dir.join(ENTRIES_FILE), text)?;
+    Ok(())
+}
+
+/// Path relative to the filesystem root, so it can be nested under a snapshot
+fn strip_root(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .collect()
+}
+
+/// Rename, falling back to copy + delete across filesystems
+fn move_path(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_recursive(from, to)?;
+    if from.is_dir() {
+        fs::remove_dir_all(from)?;
+    } else {
+        fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+    } else {
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(from, to).with_context(|| format!("Failed to copy {}", from.display()))?;
+    }
+    Ok(())
+}
+
+/// UTC timestamp usable as a directory name, e.g. `2026-01-16T09-30-00Z`
+pub fn timestamp_id(time: SystemTime) -> String {
+    utc_timestamp(time).replace(':', "-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn test_timestamp_id() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_768_555_800);
+        assert_eq!(timestamp_id(time), "2026-01-16T09-30-00Z");
+        assert_eq!(timestamp_id(UNIX_EPOCH), "1970-01-01T00-00-00Z");
+    }
+
+    #[test]
+    fn test_strip_root() {
+        assert_eq!(
+            strip_root(Path::new("/Users/joe/.gemini/skills/foo")),
+            PathBuf::from("Users/joe/.gemini/skills/foo")
+        );
+    }
+}
@@ -0,0 +1,94 @@
+//! Tool version numbers as printed by `--version`
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// First `major.minor[.patch]` in `text`, e.g. `codex-cli 0.46.0` -> 0.46.0
+    pub fn find(text: &str) -> Option<Self> {
+        text.split(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .find_map(Self::parse)
+    }
+
+    fn parse(word: &str) -> Option<Self> {
+        let mut parts = word.trim_matches('.').split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        let patch = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+        Some(Self::new(major, minor, patch))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Half-open range `[min, below)`; either end may be open
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionRange {
+    pub min: Option<Version>,
+    pub below: Option<Version>,
+}
+
+impl VersionRange {
+    pub fn contains(&self, version: &Version) -> bool {
+        self.min.is_none_or(|min| *version >= min)
+            && self.below.is_none_or(|below| *version < below)
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.min, self.below) {
+            (Some(min), Some(below)) => write!(f, ">= {}, < {}", min, below),
+            (Some(min), None) => write!(f, ">= {}", min),
+            (None, Some(below)) => write!(f, "< {}", below),
+            (None, None) => write!(f, "any"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_version_in_output() {
+        assert_eq!(Version::find("0.21.3\n"), Some(Version::new(0, 21, 3)));
+        assert_eq!(
+            Version::find("codex-cli 0.46.0"),
+            Some(Version::new(0, 46, 0))
+        );
+        assert_eq!(Version::find("v1.2"), Some(Version::new(1, 2, 0)));
+        assert_eq!(Version::find("unknown"), None);
+    }
+
+    #[test]
+    fn test_range_is_half_open() {
+        let range = VersionRange {
+            min: Some(Version::new(0, 2, 0)),
+            below: Some(Version::new(1, 0, 0)),
+        };
+        assert!(range.contains(&Version::new(0, 2, 0)));
+        assert!(range.contains(&Version::new(0, 99, 1)));
+        assert!(!range.contains(&Version::new(0, 1, 9)));
+        assert!(!range.contains(&Version::new(1, 0, 0)));
+        assert_eq!(range.to_string(), ">= 0.2.0, < 1.0.0");
+    }
+}
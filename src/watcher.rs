@@ -1,58 +1,87 @@
 //! File system watching and event handling
 
 use anyhow::{Context, Result};
-use notify::RecursiveMode;
-use notify_debouncer_mini::new_debouncer;
-use std::path::Path;
-use std::sync::mpsc::channel;
-use std::time::Duration;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 use tracing::{error, info, warn};
 
+use crate::config::{Config, WatcherSettings};
+use crate::control::{self, ControlCommand, ControlRequest};
+use crate::destination::Destination;
+use crate::preflight;
+use crate::project;
+use crate::status::WatcherHealth;
 use crate::sync::SkillSync;
+use crate::trash::Mirror;
 
-const DEBOUNCE_MS: u64 = 100;
+type FileDebouncer = Debouncer<RecommendedWatcher>;
 
-pub fn watch_and_sync(sync: SkillSync, source: &Path) -> Result<()> {
-    // Set up file watcher with debouncing
-    let (tx, rx) = channel();
+/// Everything the event loop waits on
+enum LoopEvent {
+    Files(DebounceEventResult),
+    Control(ControlRequest),
+}
 
-    let mut debouncer = new_debouncer(Duration::from_millis(DEBOUNCE_MS), tx)
-        .context("Failed to create debouncer")?;
+pub fn watch_and_sync(sync: SkillSync, config: &Config) -> Result<()> {
+    // File events and control requests share one channel
+    let (tx, rx) = channel();
 
-    // Watch source directory
-    debouncer
-        .watcher()
-        .watch(source, RecursiveMode::Recursive)
-        .with_context(|| format!("Failed to watch {}", source.display()))?;
+    let control_tx = tx.clone();
+    control::serve(&config.control_socket(), move |request| {
+        control_tx.send(LoopEvent::Control(request)).is_ok()
+    })?;
 
-    info!(path = %source.display(), "watching for changes");
+    let mut daemon = Daemon::start(sync, config, tx)?;
 
     // Set up signal handling for graceful shutdown
-    let running = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+    let running = Arc::new(AtomicBool::new(true));
     let r = running.clone();
 
     ctrlc::set_handler(move || {
         info!("shutdown signal received");
-        r.store(false, std::sync::atomic::Ordering::SeqCst);
+        r.store(false, Ordering::SeqCst);
     })
     .context("Failed to set signal handler")?;
 
+    // SIGHUP re-reads the config file, like `skillsync reload-config`
+    let hangup = Arc::new(AtomicBool::new(false));
+    signal_hook::flag::register(signal_hook::consts::SIGHUP, hangup.clone())
+        .context("Failed to set SIGHUP handler")?;
+
     // Main event loop
-    while running.load(std::sync::atomic::Ordering::SeqCst) {
-        match rx.recv_timeout(Duration::from_secs(1)) {
-            Ok(Ok(events)) => {
-                for event in events {
-                    if let Err(e) = sync.handle_event(&event.path) {
-                        warn!(
-                            path = %event.path.display(),
-                            error = %e,
-                            "failed to handle event"
-                        );
-                    }
+    while running.load(Ordering::SeqCst) && !daemon.shutdown {
+        if hangup.swap(false, Ordering::SeqCst) {
+            info!("SIGHUP received, reloading configuration");
+            // Applied by the tick, which waits while paused
+            daemon.reload_queued = true;
+        }
+        daemon.tick()?;
+
+        match rx.recv_timeout(daemon.settle.poll_interval()) {
+            Ok(LoopEvent::Files(Ok(events))) => {
+                let paths: Vec<_> = events.into_iter().map(|event| event.path).collect();
+                if daemon.projects.touches_workspace(&paths) {
+                    daemon.projects.scan_needed = true;
                 }
+                let sources = daemon.watched_sources();
+                daemon.settle.push(&sources, paths, Instant::now());
             }
-            Ok(Err(err)) => {
-                error!(error = %err, "watch error");
+            Ok(LoopEvent::Files(Err(err))) => {
+                error!(error = %err, "watch error, scheduling reconciliation");
+                daemon.rescan_needed = true;
+                daemon.health.last_error = Some(err.to_string());
+            }
+            Ok(LoopEvent::Control(request)) => {
+                let reply = daemon.control(request.command);
+                let _ = request.reply.send(reply);
             }
             Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
                 // Normal timeout, continue loop
@@ -64,6 +93,978 @@ pub fn watch_and_sync(sync: SkillSync, source: &Path) -> Result<()> {
         }
     }
 
+    let _ = fs::remove_file(config.control_socket());
     info!("skillsync daemon shutting down");
     Ok(())
 }
+
+/// State of the running event loop
+struct Daemon {
+    sync: SkillSync,
+    /// Configuration currently in effect, replaced on reload
+    config: Config,
+    tx: Sender<LoopEvent>,
+    debouncer: FileDebouncer,
+    /// One watch per source root
+    source_watches: BTreeMap<PathBuf, SourceWatch>,
+    /// Project-local syncs, when `[projects]` is configured
+    projects: Projects,
+    settle: SettleBuffer,
+    config_mtime: Option<SystemTime>,
+    started: Instant,
+    last_reconcile: Instant,
+    /// Last look for installed tools and their versions
+    last_tool_check: Instant,
+    versions: preflight::VersionProbe,
+    /// Set when the watcher reports an error, since events may have been dropped
+    rescan_needed: bool,
+    /// While paused, events are buffered and nothing is written
+    paused: bool,
+    /// A forced reload (`SIGHUP`, or `reload-config` while paused) waiting
+    /// for the next unpaused tick
+    reload_queued: bool,
+    shutdown: bool,
+    /// Watcher state as last written to the status file
+    health: WatcherHealth,
+    published_health: Option<WatcherHealth>,
+}
+
+impl Daemon {
+    fn start(sync: SkillSync, config: &Config, tx: Sender<LoopEvent>) -> Result<Self> {
+        let debouncer = start_debouncer(&config.watcher, tx.clone(), &config.sources)?;
+
+        let mut daemon = Self {
+            sync,
+            config: config.clone(),
+            settle: SettleBuffer::new(config.watcher.settle()),
+            tx,
+            debouncer,
+            source_watches: BTreeMap::new(),
+            projects: Projects::default(),
+            config_mtime: modified(&config.config_file),
+            started: Instant::now(),
+            last_reconcile: Instant::now(),
+            last_tool_check: Instant::now(),
+            versions: preflight::VersionProbe::default(),
+            rescan_needed: false,
+            paused: false,
+            reload_queued: false,
+            shutdown: false,
+            health: WatcherHealth::default(),
+            published_health: None,
+        };
+        daemon.check_sources();
+        // Projects only get the destinations of tools that are available
+        daemon.refresh_destinations(false)?;
+        if config.projects.enabled() {
+            daemon
+                .projects
+                .scan(&daemon.config, daemon.debouncer.watcher());
+        }
+        Ok(daemon)
+    }
+
+    /// Home sources and every project's skills directory
+    fn watched_sources(&self) -> Vec<PathBuf> {
+        let mut sources = self.config.sources.clone();
+        sources.extend(
+            self.projects
+                .active
+                .keys()
+                .map(|root| project::skills_dir(root)),
+        );
+        sources
+    }
+
+    /// Re-arm the watch of every source root that appeared, vanished or was
+    /// replaced. Returns true when a newly armed source needs reconciling.
+    fn check_sources(&mut self) -> bool {
+        let mut armed = false;
+        for source in &self.config.sources {
+            let watch = self.source_watches.entry(source.clone()).or_default();
+            armed |= watch.check(self.debouncer.watcher(), source);
+        }
+        armed
+    }
+
+    /// Whether every source root is being watched
+    fn sources_watched(&self) -> bool {
+        self.config.sources.iter().all(|source| {
+            self.source_watches
+                .get(source)
+                .is_some_and(|watch| watch.inode.is_some())
+        })
+    }
+
+    /// Periodic work done on every loop iteration
+    fn tick(&mut self) -> Result<()> {
+        self.tick_inner()?;
+        self.publish_health();
+        Ok(())
+    }
+
+    /// Write watcher health to the status file when it changed
+    fn publish_health(&mut self) {
+        self.health.source_watched = self.sources_watched();
+        self.health.paused = self.paused;
+        if self.published_health.as_ref() == Some(&self.health) {
+            return;
+        }
+        if let Some(status) = self.sync.status() {
+            let health = self.health.clone();
+            status.update(|s| s.watcher = health);
+        }
+        self.published_health = Some(self.health.clone());
+    }
+
+    fn tick_inner(&mut self) -> Result<()> {
+        // Pick up config file changes without a restart; a reload can fill
+        // destinations, so changes made while paused wait for the resume
+        if !self.paused {
+            let mtime = modified(&self.config.config_file);
+            if std::mem::take(&mut self.reload_queued) {
+                if let Err(e) = self.force_reload() {
+                    warn!(error = %e, "ignoring invalid config change");
+                }
+            } else if mtime != self.config_mtime {
+                self.config_mtime = mtime;
+                if let Err(e) = self.reload_config() {
+                    warn!(error = %e, "ignoring invalid config change");
+                }
+            }
+        }
+
+        if self.check_sources() {
+            // The tree under a new source root was never watched
+            self.rescan_needed = true;
+        }
+        self.projects.check(self.debouncer.watcher());
+
+        if self.paused {
+            return Ok(());
+        }
+
+        let projects = &self.config.projects;
+        let scan_due = self
+            .projects
+            .last_scan
+            .is_some_and(|last| last.elapsed() >= projects.rescan_interval());
+        if projects.enabled() && (self.projects.scan_needed || scan_due) {
+            self.projects.scan(&self.config, self.debouncer.watcher());
+        }
+
+        if self.last_tool_check.elapsed() >= self.config.tools.recheck_interval() {
+            self.last_tool_check = Instant::now();
+            if let Err(e) = self.refresh_destinations(false) {
+                warn!(error = %e, "failed to update destinations after tool check");
+            }
+        }
+
+        // One confirmation marker is shared by the home and project syncs
+        let marker = self.config.confirm_deletions_file();
+        if marker.exists() && self.held_count() > 0 {
+            if let Err(e) = fs::remove_file(&marker) {
+                warn!(path = %marker.display(), error = %e, "failed to remove confirmation");
+            }
+            if let Err(e) = self.confirm_all() {
+                warn!(error = %e, "failed to apply held deletions");
+            }
+        }
+        if let Err(e) = self.sync.poll_held_deletions() {
+            warn!(error = %e, "failed to apply held deletions");
+        }
+        self.projects.poll_held_deletions();
+
+        let ready = self.settle.take_ready(Instant::now());
+        if !ready.is_empty() {
+            let (local, home): (Vec<PathBuf>, Vec<PathBuf>) = ready
+                .into_iter()
+                .partition(|path| self.projects.project_of(path).is_some());
+            if !home.is_empty() {
+                self.sync.handle_batch(&home);
+            }
+            self.projects.handle_batch(&local);
+        }
+        self.projects.reconcile_rearmed();
+
+        let interval_elapsed = self
+            .config
+            .watcher
+            .reconcile_interval()
+            .is_some_and(|i| self.last_reconcile.elapsed() >= i);
+        if self.rescan_needed || interval_elapsed {
+            if let Err(e) = self.sync.reconcile() {
+                warn!(error = %e, "reconciliation failed");
+            }
+            self.projects.reconcile_all();
+            self.last_reconcile = Instant::now();
+            self.rescan_needed = false;
+        }
+        Ok(())
+    }
+
+    /// Reload now, regardless of whether the file looks modified
+    fn force_reload(&mut self) -> Result<bool> {
+        self.config_mtime = modified(&self.config.config_file);
+        self.reload_config()
+    }
+
+    /// Re-read the config file and apply what changed
+    ///
+    /// Buffered events stay in the settle buffer and are rendered to the new
+    /// destination list once released. Returns whether anything changed.
+    fn reload_config(&mut self) -> Result<bool> {
+        let new = self.config.reload()?;
+        let mut changed = false;
+
+        let sources_changed = new.sources != self.config.sources;
+        if sources_changed {
+            info!(sources = ?new.sources, "sources reloaded");
+            // Skills may now come from elsewhere; reconciling renders the new
+            // winners and removes skills no source provides any more
+            self.sync.set_sources(new.sources.clone());
+            self.rescan_needed = true;
+            changed = true;
+        }
+
+        if new.watcher != self.config.watcher {
+            info!(watcher = ?new.watcher, "watcher settings reloaded");
+            self.settle.window = new.watcher.settle();
+            changed = true;
+        }
+        if new.projects != self.config.projects {
+            info!(projects = ?new.projects, "project settings reloaded");
+            // Projects are rebuilt with the new destinations on the next tick
+            self.projects.clear(self.debouncer.watcher());
+            self.projects.scan_needed = new.projects.enabled();
+            changed = true;
+        }
+
+        if sources_changed || new.watcher.debounce_ms != self.config.watcher.debounce_ms {
+            // The fresh watch reports the sources as newly armed, which
+            // reconciles anything the old debouncer still held
+            self.debouncer = start_debouncer(&new.watcher, self.tx.clone(), &new.sources)?;
+            self.source_watches.clear();
+            self.projects.forget_watches();
+        }
+
+        let clean_removed = new.reload.clean_removed_destinations;
+        self.config = new;
+        if self.refresh_destinations(clean_removed)? {
+            changed = true;
+        }
+        Ok(changed)
+    }
+
+    /// Sync to the configured destinations whose tools are installed, with
+    /// renderers matching the installed versions
+    ///
+    /// Returns whether the active destination list changed.
+    fn refresh_destinations(&mut self, clean_removed: bool) -> Result<bool> {
+        let outcome = preflight::probe_all(&self.config.home, &mut self.versions);
+        if let Some(status) = self.sync.status() {
+            let tools = outcome.tool_status();
+            status.update(|s| s.tools = tools);
+        }
+
+        let projects_changed = self.projects.set_destinations(
+            preflight::enabled_project_destinations(&self.config, |spec| outcome.installed(spec)),
+            clean_removed,
+        );
+        let (enabled, warnings) = preflight::active_destinations(&self.config, &outcome);
+        if enabled == self.sync.destinations() {
+            return Ok(projects_changed);
+        }
+        for warning in &warnings {
+            warn!("{}", warning);
+        }
+        info!(
+            enabled = enabled.len(),
+            configured = self.config.destinations.len(),
+            "active destinations changed"
+        );
+        self.sync.set_destinations(enabled, clean_removed)?;
+        Ok(true)
+    }
+
+    fn control(&mut self, command: ControlCommand) -> Result<String, String> {
+        info!(command = command.name(), "control command received");
+        match command {
+            ControlCommand::Status => Ok(self.status_text()),
+            ControlCommand::Pause => {
+                self.paused = true;
+                Ok("paused\n".to_string())
+            }
+            ControlCommand::Resume => {
+                self.paused = false;
+                // Buffered events are flushed on the next tick; catch anything else too
+                self.rescan_needed = true;
+                Ok("resumed\n".to_string())
+            }
+            ControlCommand::Resync if self.paused => {
+                // Resuming reconciles anyway
+                self.rescan_needed = true;
+                Ok("paused, resync queued until resume\n".to_string())
+            }
+            ControlCommand::ReloadConfig if self.paused => {
+                self.reload_queued = true;
+                Ok("paused, reload queued until resume\n".to_string())
+            }
+            ControlCommand::Resync => {
+                let corrections = self.sync.reconcile().map_err(|e| e.to_string())?
+                    + self.projects.reconcile_all();
+                self.last_reconcile = Instant::now();
+                Ok(format!("resynced, {} correction(s)\n", corrections))
+            }
+            ControlCommand::ReloadConfig => match self.force_reload() {
+                Ok(true) => Ok("configuration reloaded\n".to_string()),
+                Ok(false) => Ok("configuration unchanged\n".to_string()),
+                Err(e) => Err(format!("{:#}", e)),
+            },
+            ControlCommand::ConfirmDeletions => {
+                let held = self.held_count();
+                if held == 0 {
+                    return Ok("no deletions are held\n".to_string());
+                }
+                self.confirm_all().map_err(|e| e.to_string())?;
+                Ok(format!("removed {} held skill(s)\n", held))
+            }
+            ControlCommand::Restore { snapshot, skill } => {
+                let mut mirrors = vec![Mirror {
+                    source: self.config.source.clone(),
+                    destinations: self.config.destinations.clone(),
+                }];
+                mirrors.extend(self.projects.mirrors());
+                let restored = self
+                    .config
+                    .trash_store()
+                    .restore_snapshot(&snapshot, skill.as_deref(), &mirrors)
+                    .map_err(|e| format!("{:#}", e))?;
+                self.projects.adopt(&restored.paths);
+                Ok(restored.report())
+            }
+            ControlCommand::Shutdown => {
+                self.shutdown = true;
+                Ok("shutting down\n".to_string())
+            }
+        }
+    }
+
+    /// Skills held by the home sync and every project
+    fn held_count(&self) -> usize {
+        self.sync.held_deletions().len() + self.projects.held_deletions().len()
+    }
+
+    /// Remove every held skill, home and project alike
+    fn confirm_all(&self) -> Result<()> {
+        self.sync.confirm_deletions()?;
+        self.projects.confirm_deletions();
+        Ok(())
+    }
+
+    fn status_text(&self) -> String {
+        let mut held = self.sync.held_deletions();
+        held.extend(self.projects.held_deletions());
+        format!(
+            "pid: {}\nuptime: {}s\npaused: {}\nsource watched: {}\nprojects: {}\npending skills: {}\nheld deletions: {}\n",
+            std::process::id(),
+            self.started.elapsed().as_secs(),
+            self.paused,
+            self.sources_watched(),
+            self.projects.active.len(),
+            self.settle.pending.len(),
+            if held.is_empty() {
+                "none".to_string()
+            } else {
+                held.join(", ")
+            },
+        )
+    }
+}
+
+/// Create a debouncer and watch each source's parent with it
+///
+/// Parents are watched non-recursively so a source root being created,
+/// removed or replaced is noticed; the sources themselves are armed by
+/// `SourceWatch`.
+fn start_debouncer(
+    settings: &WatcherSettings,
+    tx: Sender<LoopEvent>,
+    sources: &[PathBuf],
+) -> Result<FileDebouncer> {
+    let mut debouncer = new_debouncer(settings.debounce(), move |result| {
+        let _ = tx.send(LoopEvent::Files(result));
+    })
+    .context("Failed to create debouncer")?;
+
+    let parents: BTreeSet<&Path> = sources
+        .iter()
+        .filter_map(|source| source.parent())
+        .filter(|parent| parent.exists())
+        .collect();
+    for parent in parents {
+        debouncer
+            .watcher()
+            .watch(parent, RecursiveMode::NonRecursive)
+            .with_context(|| format!("Failed to watch {}", parent.display()))?;
+    }
+    info!(debounce_ms = settings.debounce_ms, "debouncer started");
+    Ok(debouncer)
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Holds events back per skill until the skill's files stop changing
+struct SettleBuffer {
+    window: Duration,
+    /// Skill name -> time of its latest event and all paths seen so far
+    pending: BTreeMap<String, (Instant, Vec<PathBuf>)>,
+}
+
+impl SettleBuffer {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Queue the paths under one of `sources`; the rest of a watched parent
+    /// (e.g. `~/.claude/settings.json`) or a workspace is dropped
+    fn push(&mut self, sources: &[PathBuf], paths: Vec<PathBuf>, now: Instant) {
+        for path in paths {
+            let Some(relative) = sources
+                .iter()
+                .find_map(|source| path.strip_prefix(source).ok())
+            else {
+                continue;
+            };
+            let skill = relative
+                .components()
+                .next()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let entry = self.pending.entry(skill).or_insert((now, Vec::new()));
+            entry.0 = now;
+            entry.1.push(path);
+        }
+    }
+
+    /// Paths of every skill that has been quiet for the settle window
+    ///
+    /// Events on a source root itself are released together with the rest,
+    /// since they may affect every skill.
+    fn take_ready(&mut self, now: Instant) -> Vec<PathBuf> {
+        let window = self.window;
+        let ready: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, (last, _))| now.duration_since(*last) >= window)
+            .map(|(skill, _)| skill.clone())
+            .collect();
+        if ready.is_empty() {
+            return Vec::new();
+        }
+
+        let mut paths = Vec::new();
+        for skill in ready {
+            if let Some((_, skill_paths)) = self.pending.remove(&skill) {
+                paths.extend(skill_paths);
+            }
+        }
+        // Root-level events must not wait behind a still-busy skill
+        if let Some((_, root_paths)) = self.pending.remove("") {
+            paths.extend(root_paths);
+        }
+        paths
+    }
+
+    /// How long to block waiting for new events
+    fn poll_interval(&self) -> Duration {
+        if self.pending.is_empty() {
+            Duration::from_secs(1)
+        } else {
+            self.window
+                .clamp(Duration::from_millis(10), Duration::from_secs(1))
+                / 2
+        }
+    }
+}
+
+/// Project-local syncs run next to the home sync
+#[derive(Default)]
+struct Projects {
+    /// Project root -> its sync
+    active: BTreeMap<PathBuf, Project>,
+    /// Enabled project destinations, relative to each project root
+    destinations: Vec<Destination>,
+    /// Workspace directories watched for projects coming and going
+    workspaces: BTreeSet<PathBuf>,
+    last_scan: Option<Instant>,
+    /// Set by events in a workspace directory
+    scan_needed: bool,
+}
+
+struct Project {
+    sync: SkillSync,
+    watch: SourceWatch,
+    /// The skills directory was (re)armed and may have missed changes
+    rescan_needed: bool,
+}
+
+impl Projects {
+    /// Start syncing projects that appeared and stop those that went away
+    ///
+    /// Outputs of a removed project stay where they are.
+    fn scan(&mut self, config: &Config, watcher: &mut dyn Watcher) {
+        self.last_scan = Some(Instant::now());
+        self.scan_needed = false;
+        let found = project::discover(&config.projects);
+
+        let gone: Vec<PathBuf> = self
+            .active
+            .keys()
+            .filter(|root| !found.contains(*root))
+            .cloned()
+            .collect();
+        for root in gone {
+            if let Some(mut project) = self.active.remove(&root) {
+                project.watch.release(watcher, &project::skills_dir(&root));
+            }
+            info!(project = %root.display(), "project removed");
+        }
+
+        for root in found {
+            if self.active.contains_key(&root) {
+                continue;
+            }
+            info!(project = %root.display(), "project added");
+            let mut watch = SourceWatch::default();
+            watch.check(watcher, &project::skills_dir(&root));
+            let sync = project::project_sync(config, &root, &self.destinations);
+            if let Err(e) = sync.initial_sync() {
+                warn!(project = %root.display(), error = %e, "initial project sync failed");
+            }
+            self.active.insert(
+                root,
+                Project {
+                    sync,
+                    watch,
+                    rescan_needed: false,
+                },
+            );
+        }
+
+        for workspace in &config.projects.workspaces {
+            if self.workspaces.contains(workspace) || !workspace.is_dir() {
+                continue;
+            }
+            match watcher.watch(workspace, RecursiveMode::NonRecursive) {
+                Ok(()) => {
+                    self.workspaces.insert(workspace.clone());
+                }
+                Err(e) => {
+                    warn!(path = %workspace.display(), error = %e, "failed to watch workspace")
+                }
+            }
+        }
+    }
+
+    /// Switch every project to `destinations`, e.g. after a tool was
+    /// installed or removed; returns whether the list changed
+    fn set_destinations(&mut self, destinations: Vec<Destination>, clean_removed: bool) -> bool {
+        if destinations == self.destinations {
+            return false;
+        }
+        info!(
+            enabled = destinations.len(),
+            "active project destinations changed"
+        );
+        self.destinations = destinations;
+        for (root, project) in &mut self.active {
+            let resolved = project::destinations(&self.destinations, root);
+            if let Err(e) = project.sync.set_destinations(resolved, clean_removed) {
+                warn!(project = %root.display(), error = %e, "failed to update project destinations");
+            }
+        }
+        true
+    }
+
+    /// Re-arm project skills directories that appeared, vanished or were replaced
+    fn check(&mut self, watcher: &mut dyn Watcher) {
+        for (root, project) in &mut self.active {
+            if project.watch.check(watcher, &project::skills_dir(root)) {
+                project.rescan_needed = true;
+            }
+        }
+    }
+
+    /// Stop every project and unwatch the workspaces
+    fn clear(&mut self, watcher: &mut dyn Watcher) {
+        for (root, mut project) in std::mem::take(&mut self.active) {
+            project.watch.release(watcher, &project::skills_dir(&root));
+        }
+        for workspace in std::mem::take(&mut self.workspaces) {
+            let _ = watcher.unwatch(&workspace);
+        }
+        self.last_scan = None;
+    }
+
+    /// After the debouncer was replaced nothing is watched any more
+    fn forget_watches(&mut self) {
+        for project in self.active.values_mut() {
+            project.watch = SourceWatch::default();
+        }
+        self.workspaces.clear();
+        // Workspaces are watched again by the next scan
+        self.scan_needed = true;
+    }
+
+    /// Root of the project whose skills directory holds `path`
+    fn project_of(&self, path: &Path) -> Option<&PathBuf> {
+        self.active
+            .keys()
+            .find(|root| path.starts_with(project::skills_dir(root)))
+    }
+
+    /// Whether `paths` include a change directly inside a workspace
+    fn touches_workspace(&self, paths: &[PathBuf]) -> bool {
+        paths.iter().any(|path| {
+            path.parent()
+                .is_some_and(|parent| self.workspaces.contains(parent))
+        })
+    }
+
+    /// Hand each project the events under its skills directory
+    fn handle_batch(&self, paths: &[PathBuf]) {
+        for (root, project) in &self.active {
+            let skills = project::skills_dir(root);
+            let own: Vec<PathBuf> = paths
+                .iter()
+                .filter(|path| path.starts_with(&skills))
+                .cloned()
+                .collect();
+            if !own.is_empty() {
+                project.sync.handle_batch(&own);
+            }
+        }
+    }
+
+    /// Each project's skills directory and its active destinations
+    fn mirrors(&self) -> Vec<Mirror> {
+        self.active
+            .iter()
+            .map(|(root, project)| Mirror {
+                source: project::skills_dir(root),
+                destinations: project.sync.destinations().to_vec(),
+            })
+            .collect()
+    }
+
+    /// Record restored entries in the manifests of the projects they belong to
+    fn adopt(&self, restored: &[PathBuf]) {
+        for (root, project) in &self.active {
+            if restored.iter().any(|path| path.starts_with(root)) {
+                project.sync.adopt(restored);
+            }
+        }
+    }
+
+    /// Held skills of every project, as `<project>: <skill>`
+    fn held_deletions(&self) -> Vec<String> {
+        self.active
+            .iter()
+            .flat_map(|(root, project)| {
+                project
+                    .sync
+                    .held_deletions()
+                    .into_iter()
+                    .map(move |skill| format!("{}: {}", root.display(), skill))
+            })
+            .collect()
+    }
+
+    /// Release held deletions whose skills reappeared
+    fn poll_held_deletions(&self) {
+        for (root, project) in &self.active {
+            if let Err(e) = project.sync.poll_held_deletions() {
+                warn!(project = %root.display(), error = %e, "failed to apply held deletions");
+            }
+        }
+    }
+
+    /// Remove every project's held skills
+    fn confirm_deletions(&self) {
+        for (root, project) in &self.active {
+            if let Err(e) = project.sync.confirm_deletions() {
+                warn!(project = %root.display(), error = %e, "failed to apply held deletions");
+            }
+        }
+    }
+
+    /// Reconcile projects whose skills directory was just armed
+    fn reconcile_rearmed(&mut self) {
+        for (root, project) in &mut self.active {
+            if std::mem::take(&mut project.rescan_needed) {
+                reconcile_project(root, &project.sync);
+            }
+        }
+    }
+
+    /// Reconcile every project, returning the number of corrections
+    fn reconcile_all(&mut self) -> usize {
+        self.active
+            .iter_mut()
+            .map(|(root, project)| {
+                project.rescan_needed = false;
+                reconcile_project(root, &project.sync)
+            })
+            .sum()
+    }
+}
+
+fn reconcile_project(root: &Path, sync: &SkillSync) -> usize {
+    sync.reconcile().unwrap_or_else(|e| {
+        warn!(project = %root.display(), error = %e, "project reconciliation failed");
+        0
+    })
+}
+
+/// Tracks which incarnation of the source directory is being watched
+#[derive(Default)]
+struct SourceWatch {
+    /// Inode of the watched source root, or `None` while unwatched
+    inode: Option<u64>,
+}
+
+impl SourceWatch {
+    /// Stop watching `source`, e.g. because its project went away
+    fn release(&mut self, watcher: &mut dyn Watcher, source: &Path) {
+        if self.inode.take().is_some() {
+            let _ = watcher.unwatch(source);
+        }
+    }
+
+    /// Re-arm the recursive watch if the source root appeared, vanished or
+    /// was replaced. Returns true when a newly armed source needs reconciling.
+    fn check(&mut self, watcher: &mut dyn Watcher, source: &Path) -> bool {
+        let current = fs::metadata(source)
+            .ok()
+            .filter(|m| m.is_dir())
+            .map(|m| m.ino());
+        if current == self.inode {
+            return false;
+        }
+
+        if self.inode.is_some() {
+            // The old inode is gone, so unwatching may legitimately fail
+            let _ = watcher.unwatch(source);
+        }
+
+        match current {
+            None => {
+                warn!(
+                    path = %source.display(),
+                    "source directory disappeared; keeping destinations and waiting for it to return"
+                );
+                self.inode = None;
+                false
+            }
+            Some(inode) => {
+                if let Err(e) = watcher.watch(source, RecursiveMode::Recursive) {
+                    error!(path = %source.display(), error = %e, "failed to watch source");
+                    self.inode = None;
+                    return false;
+                }
+                let replaced = self.inode.is_some();
+                self.inode = Some(inode);
+                info!(path = %source.display(), replaced, "watching for changes");
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::NullWatcher;
+
+    #[test]
+    fn test_settle_buffer_waits_for_quiet_skill() {
+        let source = Path::new("/skills");
+        let sources = [source.to_path_buf()];
+        let mut settle = SettleBuffer::new(Duration::from_millis(500));
+        let start = Instant::now();
+
+        settle.push(&sources, vec![source.join("busy/SKILL.md")], start);
+        settle.push(&sources, vec![source.join("done/SKILL.md")], start);
+        // "busy" keeps changing, "done" goes quiet
+        let later = start + Duration::from_millis(400);
+        settle.push(&sources, vec![source.join("busy/notes.md")], later);
+
+        assert!(settle
+            .take_ready(start + Duration::from_millis(300))
+            .is_empty());
+        assert_eq!(
+            settle.take_ready(start + Duration::from_millis(600)),
+            vec![source.join("done/SKILL.md")]
+        );
+        assert_eq!(
+            settle.take_ready(later + Duration::from_millis(500)),
+            vec![source.join("busy/SKILL.md"), source.join("busy/notes.md")]
+        );
+    }
+
+    #[test]
+    fn test_settle_buffer_disabled_releases_immediately() {
+        let source = Path::new("/skills");
+        let sources = [source.to_path_buf()];
+        let mut settle = SettleBuffer::new(Duration::ZERO);
+        let now = Instant::now();
+
+        settle.push(&sources, vec![source.join("a/SKILL.md")], now);
+        assert_eq!(settle.take_ready(now), vec![source.join("a/SKILL.md")]);
+    }
+
+    #[test]
+    fn test_settle_buffer_drops_paths_outside_sources() {
+        let source = Path::new("/home/me/.claude/skills");
+        let sources = [source.to_path_buf()];
+        let mut settle = SettleBuffer::new(Duration::ZERO);
+        let now = Instant::now();
+
+        let paths = vec![
+            PathBuf::from("/home/me/.claude/settings.json"),
+            PathBuf::from("/home/me/code/new-repo"),
+            source.to_path_buf(),
+        ];
+        settle.push(&sources, paths, now);
+        assert_eq!(settle.take_ready(now), vec![source.to_path_buf()]);
+    }
+
+    #[test]
+    fn test_source_watch_tracks_root_lifecycle() {
+        let parent = tempfile::TempDir::new().unwrap();
+        let source = parent.path().join("skills");
+        let mut watcher = NullWatcher;
+        let mut source_watch = SourceWatch::default();
+
+        // Missing source: nothing to arm
+        assert!(!source_watch.check(&mut watcher, &source));
+
+        // Created: armed once
+        fs::create_dir(&source).unwrap();
+        assert!(source_watch.check(&mut watcher, &source));
+        assert!(!source_watch.check(&mut watcher, &source));
+
+        // Replaced by a new directory: re-armed
+        let moved = parent.path().join("old-skills");
+        fs::rename(&source, &moved).unwrap();
+        fs::create_dir(&source).unwrap();
+        assert!(source_watch.check(&mut watcher, &source));
+
+        // Removed: disarmed until it returns
+        fs::remove_dir(&source).unwrap();
+        assert!(!source_watch.check(&mut watcher, &source));
+        assert_eq!(source_watch.inode, None);
+    }
+
+    #[test]
+    fn test_projects_follow_workspace() {
+        let home = tempfile::TempDir::new().unwrap();
+        let app_dir = home.path().join("skillsync");
+        fs::create_dir_all(&app_dir).unwrap();
+        fs::write(
+            app_dir.join("config.toml"),
+            "[trash]\nenabled = false\n\n[projects]\nworkspaces = [\"~/src\"]\n",
+        )
+        .unwrap();
+        let config = Config::for_home(home.path()).unwrap();
+        let repo = home.path().join("src").join("app");
+        let skill = project::skills_dir(&repo).join("build");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "---\ndescription: v1\n---\n").unwrap();
+
+        let mut watcher = NullWatcher;
+        let mut projects = Projects {
+            destinations: config.projects.destinations.clone(),
+            ..Projects::default()
+        };
+        projects.scan(&config, &mut watcher);
+        assert_eq!(projects.active.len(), 1);
+        let command = repo.join(".gemini").join("commands").join("build.toml");
+        assert!(fs::read_to_string(&command).unwrap().contains("v1"));
+
+        // Events under the project's skills directory go to its sync
+        fs::write(skill.join("SKILL.md"), "---\ndescription: v2\n---\n").unwrap();
+        let event = skill.join("SKILL.md");
+        assert_eq!(projects.project_of(&event), Some(&repo));
+        assert!(projects.touches_workspace(std::slice::from_ref(&repo)));
+        projects.handle_batch(&[event]);
+        assert!(fs::read_to_string(&command).unwrap().contains("v2"));
+
+        // A project whose skills are gone is no longer synced
+        fs::remove_dir_all(repo.join(".claude")).unwrap();
+        projects.scan(&config, &mut watcher);
+        assert!(projects.active.is_empty());
+        assert!(command.exists());
+    }
+
+    #[test]
+    fn test_paused_daemon_queues_resync_and_reload() {
+        let home = tempfile::TempDir::new().unwrap();
+        let app_dir = home.path().join("skillsync");
+        fs::create_dir_all(&app_dir).unwrap();
+        let out = "[trash]\nenabled = false\n\n[[destinations]]\npath = \"~/out\"\ntype = \"claude-style\"\n";
+        fs::write(app_dir.join("config.toml"), out).unwrap();
+        let config = Config::for_home(home.path()).unwrap();
+        fs::create_dir_all(&config.source).unwrap();
+
+        let sync = SkillSync::new(config.source.clone(), config.destinations.clone());
+        let (tx, _rx) = channel();
+        let mut daemon = Daemon::start(sync, &config, tx).unwrap();
+        daemon.control(ControlCommand::Pause).unwrap();
+
+        let skill = config.source.join("build");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "---\ndescription: Build\n---\n").unwrap();
+        fs::write(
+            app_dir.join("config.toml"),
+            format!(
+                "{}\n[[destinations]]\npath = \"~/more\"\ntype = \"claude-style\"\n",
+                out
+            ),
+        )
+        .unwrap();
+
+        assert!(daemon
+            .control(ControlCommand::Resync)
+            .unwrap()
+            .contains("queued"));
+        assert!(daemon
+            .control(ControlCommand::ReloadConfig)
+            .unwrap()
+            .contains("queued"));
+        daemon.tick().unwrap();
+        assert!(!home.path().join("out").join("build").exists());
+        assert!(!home.path().join("more").exists());
+
+        daemon.control(ControlCommand::Resume).unwrap();
+        daemon.tick().unwrap();
+        assert!(home
+            .path()
+            .join("out")
+            .join("build")
+            .join("SKILL.md")
+            .exists());
+        assert!(home
+            .path()
+            .join("more")
+            .join("build")
+            .join("SKILL.md")
+            .exists());
+    }
+}
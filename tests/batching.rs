@@ -0,0 +1,95 @@
+mod common;
+
+use common::Fixture;
+use std::fs;
+
+#[test]
+fn batch_of_file_events_syncs_skill_and_prunes_deleted_files() {
+    let fixture = Fixture::new();
+    let sync = fixture.sync();
+    let skill = fixture.source.path().join("many-files");
+    fs::create_dir_all(skill.join("references")).unwrap();
+    fs::write(skill.join("SKILL.md"), "---\ndescription: v1\n---\nBody").unwrap();
+    for i in 0..30 {
+        fs::write(skill.join("references").join(format!("{}.md", i)), "ref").unwrap();
+    }
+    sync.initial_sync().unwrap();
+
+    fs::write(skill.join("SKILL.md"), "---\ndescription: v2\n---\nBody").unwrap();
+    fs::remove_file(skill.join("references").join("7.md")).unwrap();
+    let mut events = vec![skill.join("SKILL.md"), skill.join("references")];
+    events.extend((0..30).map(|i| skill.join("references").join(format!("{}.md", i))));
+    sync.handle_batch(&events);
+
+    let dest_refs = fixture.claude.path().join("many-files").join("references");
+    assert!(!dest_refs.join("7.md").exists());
+    assert!(dest_refs.join("8.md").exists());
+    let rendered = fs::read_to_string(fixture.toml.path().join("many-files.toml")).unwrap();
+    assert!(rendered.contains("description = \"v2\""));
+}
+
+#[test]
+fn directory_only_event_removes_toml_of_deleted_skill() {
+    let fixture = Fixture::new();
+    let sync = fixture.sync();
+    let skill = fixture.source.path().join("gone");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(skill.join("SKILL.md"), "Body").unwrap();
+    sync.initial_sync().unwrap();
+    assert!(fixture.toml.path().join("gone.toml").exists());
+
+    fs::remove_dir_all(&skill).unwrap();
+    sync.handle_batch(&[skill]);
+
+    assert!(!fixture.claude.path().join("gone").exists());
+    assert!(!fixture.toml.path().join("gone.toml").exists());
+}
+
+#[test]
+fn removing_skill_md_drops_toml_but_keeps_other_files() {
+    let fixture = Fixture::new();
+    let sync = fixture.sync();
+    let skill = fixture.source.path().join("partial");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(skill.join("SKILL.md"), "Body").unwrap();
+    fs::write(skill.join("notes.md"), "Notes").unwrap();
+    sync.initial_sync().unwrap();
+
+    fs::remove_file(skill.join("SKILL.md")).unwrap();
+    sync.handle_batch(&[skill.join("SKILL.md")]);
+
+    assert!(!fixture.toml.path().join("partial.toml").exists());
+    assert!(!fixture
+        .claude
+        .path()
+        .join("partial")
+        .join("SKILL.md")
+        .exists());
+    assert!(fixture
+        .claude
+        .path()
+        .join("partial")
+        .join("notes.md")
+        .exists());
+}
+
+#[test]
+fn batch_is_ignored_while_source_root_is_missing() {
+    let fixture = Fixture::new();
+    let sync = fixture.sync();
+    let skill = fixture.source.path().join("kept");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(skill.join("SKILL.md"), "Body").unwrap();
+    sync.initial_sync().unwrap();
+
+    // The whole source root disappears, e.g. a dotfile manager replacing it
+    fs::remove_dir_all(fixture.source.path()).unwrap();
+    sync.handle_batch(&[
+        fixture.source.path().to_path_buf(),
+        skill.clone(),
+        skill.join("SKILL.md"),
+    ]);
+
+    assert!(fixture.claude.path().join("kept").join("SKILL.md").exists());
+    assert!(fixture.toml.path().join("kept.toml").exists());
+}
@@ -1,2 +1,83 @@
 // Common test utilities
-// Currently empty but can be expanded with shared test helpers
+// Not every test crate uses every helper
+#![allow(dead_code)]
+
+use skillsync::safety::DeletionGuard;
+use skillsync::trash::Trash;
+use skillsync::{Destination, DestinationType, SkillSync};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// Write `content` to `path`, creating its parent directories
+pub fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+/// Write a minimal `<root>/<name>/SKILL.md` described by its name
+pub fn write_skill(root: &Path, name: &str) {
+    write(
+        &root.join(name).join("SKILL.md"),
+        &format!("---\ndescription: {}\n---\nBody", name),
+    );
+}
+
+/// Temporary source, Claude-style and Gemini TOML destinations and state
+/// directory, from which tests build the `SkillSync` they need
+pub struct Fixture {
+    pub source: TempDir,
+    pub claude: TempDir,
+    pub toml: TempDir,
+    pub state: TempDir,
+}
+
+impl Fixture {
+    pub fn new() -> Self {
+        Self {
+            source: TempDir::new().unwrap(),
+            claude: TempDir::new().unwrap(),
+            toml: TempDir::new().unwrap(),
+            state: TempDir::new().unwrap(),
+        }
+    }
+
+    pub fn destinations(&self) -> Vec<Destination> {
+        vec![
+            Destination::new(
+                self.claude.path().to_path_buf(),
+                DestinationType::ClaudeStyle,
+            ),
+            Destination::new(self.toml.path().to_path_buf(), DestinationType::GeminiToml),
+        ]
+    }
+
+    /// Trash under the state directory keeping 10 snapshots for 30 days
+    pub fn trash(&self) -> Trash {
+        Trash::new(
+            self.state.path().join("trash"),
+            10,
+            Duration::from_secs(30 * 24 * 60 * 60),
+        )
+    }
+
+    /// The default deletion guard, confirmed by a marker in the state directory
+    pub fn guard(&self) -> DeletionGuard {
+        DeletionGuard {
+            max_fraction: 0.5,
+            max_count: 20,
+            confirm_file: self.confirm_file(),
+            window: Duration::from_secs(60),
+        }
+    }
+
+    pub fn confirm_file(&self) -> PathBuf {
+        self.state.path().join("confirm-deletions")
+    }
+
+    /// Sync from the source to both destinations; add `with_*` as needed
+    pub fn sync(&self) -> SkillSync {
+        SkillSync::new(self.source.path().to_path_buf(), self.destinations())
+    }
+}
@@ -31,3 +31,278 @@ fn test_config_has_codex_destination() {
         "Config should include ~/.cursor/skills destination"
     );
 }
+
+#[test]
+fn test_config_file_overrides_trash_settings() {
+    let home = tempfile::TempDir::new().unwrap();
+    let app_dir = home.path().join("skillsync");
+    std::fs::create_dir_all(&app_dir).unwrap();
+    std::fs::write(
+        app_dir.join("config.toml"),
+        "[trash]\nenabled = false\nmax_snapshots = 3\n",
+    )
+    .unwrap();
+
+    let config = Config::for_home(home.path()).expect("Failed to load config");
+
+    assert!(!config.trash.enabled);
+    assert_eq!(config.trash.max_snapshots, 3);
+    assert_eq!(config.trash.max_age_days, 30);
+    assert_eq!(config.state_dir, app_dir.join("state"));
+}
+
+#[test]
+fn test_config_file_rejects_zero_max_snapshots() {
+    let home = tempfile::TempDir::new().unwrap();
+    let app_dir = home.path().join("skillsync");
+    std::fs::create_dir_all(&app_dir).unwrap();
+    std::fs::write(app_dir.join("config.toml"), "[trash]\nmax_snapshots = 0\n").unwrap();
+
+    let err = Config::for_home(home.path()).unwrap_err();
+    assert!(format!("{:#}", err).contains("max_snapshots"));
+
+    // A disabled trash never prunes, so the value does not matter
+    std::fs::write(
+        app_dir.join("config.toml"),
+        "[trash]\nenabled = false\nmax_snapshots = 0\n",
+    )
+    .unwrap();
+    assert!(Config::for_home(home.path()).is_ok());
+}
+
+#[test]
+fn test_config_file_rejects_unknown_keys() {
+    let home = tempfile::TempDir::new().unwrap();
+    let app_dir = home.path().join("skillsync");
+    std::fs::create_dir_all(&app_dir).unwrap();
+    std::fs::write(app_dir.join("config.toml"), "[trash]\nkeep = 3\n").unwrap();
+
+    assert!(Config::for_home(home.path()).is_err());
+}
+
+#[test]
+fn test_watcher_settings_reload_from_file() {
+    let home = tempfile::TempDir::new().unwrap();
+    let config_file = home.path().join("config.toml");
+
+    let defaults = skillsync::config::WatcherSettings::load(&config_file).unwrap();
+    assert_eq!(defaults.debounce_ms, 100);
+    assert_eq!(defaults.settle_ms, 0);
+
+    std::fs::write(
+        &config_file,
+        "[watcher]\ndebounce_ms = 400\nsettle_ms = 1500\n",
+    )
+    .unwrap();
+    let reloaded = skillsync::config::WatcherSettings::load(&config_file).unwrap();
+    assert_eq!(reloaded.debounce_ms, 400);
+    assert_eq!(reloaded.settle_ms, 1500);
+    assert_ne!(reloaded, defaults);
+}
+
+#[test]
+fn test_config_file_replaces_destinations() {
+    let home = tempfile::TempDir::new().unwrap();
+    let app_dir = home.path().join("skillsync");
+    std::fs::create_dir_all(&app_dir).unwrap();
+    std::fs::write(
+        app_dir.join("config.toml"),
+        r#"
+[[destinations]]
+path = "~/.cursor/skills"
+type = "claude-style"
+
+[[destinations]]
+path = "/tmp/skillsync-commands"
+type = "gemini-toml"
+
+[destinations.fields]
+allowed-tools = ""
+"#,
+    )
+    .unwrap();
+
+    let config = Config::for_home(home.path()).expect("Failed to load config");
+
+    assert_eq!(
+        config.destinations,
+        vec![
+            // The tool is inferred from a known skills directory
+            skillsync::Destination::new(
+                home.path().join(".cursor/skills"),
+                skillsync::DestinationType::ClaudeStyle
+            )
+            .with_tool("cursor"),
+            skillsync::Destination::new(
+                "/tmp/skillsync-commands".into(),
+                skillsync::DestinationType::GeminiToml
+            )
+            .with_fields([("allowed-tools".to_string(), String::new())].into()),
+        ]
+    );
+    assert!(!config.reload.clean_removed_destinations);
+
+    std::fs::write(
+        app_dir.join("config.toml"),
+        "[[destinations]]\npath = \"relative/skills\"\ntype = \"claude-style\"\n",
+    )
+    .unwrap();
+    assert!(config.reload().is_err());
+}
+
+#[test]
+fn test_fields_keep_list() {
+    let home = tempfile::TempDir::new().unwrap();
+    let app_dir = home.path().join("skillsync");
+    std::fs::create_dir_all(&app_dir).unwrap();
+    std::fs::write(
+        app_dir.join("config.toml"),
+        r#"
+[[destinations]]
+path = "~/.codex/skills"
+type = "claude-style"
+fields = { keep = ["name", "description"], description = "summary" }
+"#,
+    )
+    .unwrap();
+
+    let config = Config::for_home(home.path()).expect("Failed to load config");
+    let fields = &config.destinations[0].fields;
+    assert_eq!(
+        fields.keep.as_deref(),
+        Some(&["name".to_string(), "description".to_string()][..])
+    );
+    assert_eq!(fields.rename["description"], "summary");
+
+    // The former `frontmatter` filter is folded into `fields`
+    std::fs::write(
+        app_dir.join("config.toml"),
+        "[[destinations]]\npath = \"~/.codex/skills\"\ntype = \"claude-style\"\n\
+         frontmatter = { drop = [\"model\"] }\n",
+    )
+    .unwrap();
+    assert!(config.reload().is_err());
+}
+
+#[test]
+fn test_inline_only_for_gemini_toml() {
+    let home = tempfile::TempDir::new().unwrap();
+    let app_dir = home.path().join("skillsync");
+    std::fs::create_dir_all(&app_dir).unwrap();
+    std::fs::write(
+        app_dir.join("config.toml"),
+        "[[destinations]]\npath = \"~/.gemini/commands\"\ntype = \"gemini-toml\"\n\n\
+         [destinations.inline]\nmax_file_bytes = 1000\n",
+    )
+    .unwrap();
+
+    let config = Config::for_home(home.path()).expect("Failed to load config");
+    let inline = config.destinations[0].inline.as_ref().unwrap();
+    assert_eq!(inline.max_file_bytes, 1000);
+    assert_eq!(inline.max_total_bytes, 256 * 1024);
+
+    std::fs::write(
+        app_dir.join("config.toml"),
+        "[[destinations]]\npath = \"~/.codex/skills\"\ntype = \"claude-style\"\ninline = {}\n",
+    )
+    .unwrap();
+    assert!(config.reload().is_err());
+}
+
+#[test]
+fn test_rewrite_paths_per_destination() {
+    let home = tempfile::TempDir::new().unwrap();
+    let app_dir = home.path().join("skillsync");
+    std::fs::create_dir_all(&app_dir).unwrap();
+    std::fs::write(
+        app_dir.join("config.toml"),
+        "[[destinations]]\npath = \"~/.codex/skills\"\ntype = \"claude-style\"\nrewrite_paths = true\n\n\
+         [[destinations]]\npath = \"~/.gemini/commands\"\ntype = \"gemini-toml\"\n",
+    )
+    .unwrap();
+
+    let config = Config::for_home(home.path()).expect("Failed to load config");
+    assert!(config.destinations[0].rewrite_paths);
+    assert!(!config.destinations[1].rewrite_paths);
+}
+
+#[test]
+fn test_sources_ordered_by_priority() {
+    let home = tempfile::TempDir::new().unwrap();
+    let app_dir = home.path().join("skillsync");
+    std::fs::create_dir_all(&app_dir).unwrap();
+
+    let config = Config::for_home(home.path()).expect("Failed to load config");
+    assert_eq!(config.sources, vec![home.path().join(".claude/skills")]);
+
+    std::fs::write(
+        app_dir.join("config.toml"),
+        "[[sources]]\npath = \"/srv/team/skills\"\npriority = 10\n\n\
+         [[sources]]\npath = \"~/.claude/skills\"\npriority = 20\n\n\
+         [[sources]]\npath = \"~/extra\"\n",
+    )
+    .unwrap();
+    let config = config.reload().expect("Failed to reload config");
+    assert_eq!(
+        config.sources,
+        vec![
+            home.path().join(".claude/skills"),
+            std::path::PathBuf::from("/srv/team/skills"),
+            home.path().join("extra"),
+        ]
+    );
+    assert_eq!(config.source, home.path().join(".claude/skills"));
+
+    // Without Claude's directory, imports go to the top synced source
+    std::fs::write(
+        app_dir.join("config.toml"),
+        "[[sources]]\npath = \"/srv/team/skills\"\n",
+    )
+    .unwrap();
+    let config = config.reload().expect("Failed to reload config");
+    assert_eq!(config.source, std::path::PathBuf::from("/srv/team/skills"));
+
+    std::fs::write(app_dir.join("config.toml"), "sources = []\n").unwrap();
+    assert!(config.reload().is_err());
+}
+
+#[test]
+fn test_project_destinations_are_relative() {
+    let home = tempfile::TempDir::new().unwrap();
+    let app_dir = home.path().join("skillsync");
+    std::fs::create_dir_all(&app_dir).unwrap();
+
+    let config = Config::for_home(home.path()).expect("Failed to load config");
+    assert!(!config.projects.enabled());
+    assert!(config.projects.destinations.contains(
+        &skillsync::Destination::new(
+            ".gemini/commands".into(),
+            skillsync::DestinationType::GeminiToml
+        )
+        .with_tool("gemini")
+    ));
+
+    std::fs::write(
+        app_dir.join("config.toml"),
+        "[projects]\nworkspaces = [\"~/src\"]\n\n\
+         [[projects.destinations]]\npath = \".codex/skills\"\ntype = \"claude-style\"\n",
+    )
+    .unwrap();
+    let config = config.reload().expect("Failed to reload config");
+    assert_eq!(config.projects.workspaces, vec![home.path().join("src")]);
+    assert_eq!(
+        config.projects.destinations,
+        vec![skillsync::Destination::new(
+            ".codex/skills".into(),
+            skillsync::DestinationType::ClaudeStyle
+        )
+        .with_tool("codex")]
+    );
+
+    std::fs::write(
+        app_dir.join("config.toml"),
+        "[[projects.destinations]]\npath = \"~/.codex/skills\"\ntype = \"claude-style\"\n",
+    )
+    .unwrap();
+    assert!(config.reload().is_err());
+}
@@ -0,0 +1,91 @@
+use skillsync::control::{self, ControlCommand};
+use tempfile::TempDir;
+
+#[test]
+fn control_socket_round_trip() {
+    let dir = TempDir::new().unwrap();
+    let socket = dir.path().join("state").join("skillsync.sock");
+
+    control::serve(&socket, |request| {
+        let reply = match request.command {
+            ControlCommand::Status => Ok("paused: false\n".to_string()),
+            ControlCommand::Resync => Err("source missing".to_string()),
+            other => Ok(format!("{}\n", other.name())),
+        };
+        request.reply.send(reply).is_ok()
+    })
+    .unwrap();
+
+    assert_eq!(
+        control::send(&socket, ControlCommand::Status).unwrap(),
+        "paused: false\n"
+    );
+    assert_eq!(
+        control::send(&socket, ControlCommand::ReloadConfig).unwrap(),
+        "reload-config\n"
+    );
+    let err = control::send(&socket, ControlCommand::Resync).unwrap_err();
+    assert!(err.to_string().contains("source missing"));
+}
+
+#[test]
+fn send_without_daemon_explains_failure() {
+    let dir = TempDir::new().unwrap();
+    let err = control::send(&dir.path().join("missing.sock"), ControlCommand::Pause).unwrap_err();
+    assert!(err.to_string().contains("is it running?"));
+}
+
+#[test]
+fn command_names_round_trip() {
+    for command in [
+        ControlCommand::Status,
+        ControlCommand::Pause,
+        ControlCommand::Resume,
+        ControlCommand::Resync,
+        ControlCommand::ReloadConfig,
+        ControlCommand::ConfirmDeletions,
+        ControlCommand::Shutdown,
+    ] {
+        assert_eq!(ControlCommand::parse(command.name()), Some(command));
+    }
+    assert_eq!(ControlCommand::parse("reboot"), None);
+}
+
+#[test]
+fn restore_command_carries_arguments() {
+    let command = ControlCommand::Restore {
+        snapshot: "latest".to_string(),
+        skill: Some("pdf".to_string()),
+    };
+    assert_eq!(command.to_line(), "restore latest pdf");
+    assert_eq!(ControlCommand::parse(&command.to_line()), Some(command));
+    assert_eq!(
+        ControlCommand::parse("restore 2026-01-16T09-30-00Z"),
+        Some(ControlCommand::Restore {
+            snapshot: "2026-01-16T09-30-00Z".to_string(),
+            skill: None,
+        })
+    );
+    assert_eq!(ControlCommand::parse("restore"), None);
+    assert_eq!(ControlCommand::parse("restore \"latest"), None);
+    assert_eq!(ControlCommand::parse("pause now"), None);
+}
+
+#[test]
+fn restore_arguments_keep_spaces_and_quotes() {
+    let command = ControlCommand::Restore {
+        snapshot: "latest".to_string(),
+        skill: Some("my \"big\" skill\\".to_string()),
+    };
+    assert_eq!(
+        command.to_line(),
+        "restore latest \"my \\\"big\\\" skill\\\\\""
+    );
+    assert_eq!(ControlCommand::parse(&command.to_line()), Some(command));
+
+    let command = ControlCommand::Restore {
+        snapshot: "".to_string(),
+        skill: None,
+    };
+    assert_eq!(ControlCommand::parse(&command.to_line()), Some(command));
+}
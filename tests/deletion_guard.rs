@@ -0,0 +1,158 @@
+mod common;
+
+use common::{write_skill, Fixture};
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn emptied_source_holds_orphan_cleanup_until_confirmed() {
+    let fixture = Fixture::new();
+    for name in ["a", "b", "c", "d"] {
+        write_skill(fixture.claude.path(), name);
+    }
+
+    let sync = fixture.sync().with_deletion_guard(fixture.guard());
+    sync.initial_sync().unwrap();
+
+    // Every skill would be removed, so nothing is
+    assert!(fixture.claude.path().join("a").join("SKILL.md").exists());
+    assert_eq!(sync.held_deletions(), vec!["a", "b", "c", "d"]);
+
+    // An orphan that appears after the hold is not part of the confirmation
+    write_skill(fixture.claude.path(), "stray");
+
+    fs::write(fixture.confirm_file(), "").unwrap();
+    sync.poll_held_deletions().unwrap();
+
+    assert!(sync.held_deletions().is_empty());
+    assert!(!fixture.claude.path().join("a").exists());
+    assert!(fixture
+        .claude
+        .path()
+        .join("stray")
+        .join("SKILL.md")
+        .exists());
+    assert!(!fixture.confirm_file().exists());
+}
+
+#[test]
+fn held_deletions_release_when_source_returns() {
+    let fixture = Fixture::new();
+    for name in ["a", "b", "c"] {
+        write_skill(fixture.source.path(), name);
+    }
+
+    let sync = fixture.sync().with_deletion_guard(fixture.guard());
+    sync.initial_sync().unwrap();
+
+    // Simulate a checkout that briefly removes every skill
+    let moved = TempDir::new().unwrap();
+    for name in ["a", "b", "c"] {
+        fs::rename(fixture.source.path().join(name), moved.path().join(name)).unwrap();
+    }
+    let events: Vec<_> = ["a", "b", "c"]
+        .iter()
+        .map(|name| fixture.source.path().join(name))
+        .collect();
+    sync.handle_batch(&events);
+
+    assert!(fixture.claude.path().join("b").join("SKILL.md").exists());
+    assert_eq!(sync.held_deletions().len(), 3);
+
+    for name in ["a", "b", "c"] {
+        fs::rename(moved.path().join(name), fixture.source.path().join(name)).unwrap();
+    }
+    sync.poll_held_deletions().unwrap();
+    assert!(sync.held_deletions().is_empty());
+}
+
+#[test]
+fn single_skill_deletion_is_not_held() {
+    let fixture = Fixture::new();
+    write_skill(fixture.source.path(), "only");
+
+    let sync = fixture.sync().with_deletion_guard(fixture.guard());
+    sync.initial_sync().unwrap();
+
+    fs::remove_dir_all(fixture.source.path().join("only")).unwrap();
+    sync.handle_batch(&[fixture.source.path().join("only")]);
+
+    assert!(!fixture.claude.path().join("only").exists());
+    assert!(sync.held_deletions().is_empty());
+}
+
+#[test]
+fn deletion_split_across_batches_is_held() {
+    let fixture = Fixture::new();
+    let names = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"];
+    for name in names {
+        write_skill(fixture.source.path(), name);
+    }
+
+    let sync = fixture.sync().with_deletion_guard(fixture.guard());
+    sync.initial_sync().unwrap();
+
+    // Removing 6 of 10 in batches of 2; each batch alone is under half of
+    // the skills left at that point
+    for batch in names[..6].chunks(2) {
+        let events: Vec<_> = batch
+            .iter()
+            .map(|name| {
+                fs::remove_dir_all(fixture.source.path().join(name)).unwrap();
+                fixture.source.path().join(name)
+            })
+            .collect();
+        sync.handle_batch(&events);
+    }
+
+    assert!(!fixture.claude.path().join("a").exists());
+    assert!(!fixture.claude.path().join("d").exists());
+    assert!(fixture.claude.path().join("e").join("SKILL.md").exists());
+    assert_eq!(sync.held_deletions(), vec!["e", "f"]);
+}
+
+#[test]
+fn released_hold_starts_a_new_window() {
+    let fixture = Fixture::new();
+    let names = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"];
+    for name in names {
+        write_skill(fixture.source.path(), name);
+    }
+
+    let sync = fixture.sync().with_deletion_guard(fixture.guard());
+    sync.initial_sync().unwrap();
+
+    let remove = |batch: &[&str]| {
+        let events: Vec<_> = batch
+            .iter()
+            .map(|name| {
+                fs::remove_dir_all(fixture.source.path().join(name)).unwrap();
+                fixture.source.path().join(name)
+            })
+            .collect();
+        sync.handle_batch(&events);
+    };
+
+    // Two real removals, then a checkout that drops six more and comes back
+    remove(&["a", "b"]);
+    let moved = TempDir::new().unwrap();
+    for name in &names[2..8] {
+        fs::rename(fixture.source.path().join(name), moved.path().join(name)).unwrap();
+    }
+    let events: Vec<_> = names[2..8]
+        .iter()
+        .map(|name| fixture.source.path().join(name))
+        .collect();
+    sync.handle_batch(&events);
+    assert_eq!(sync.held_deletions().len(), 6);
+    for name in &names[2..8] {
+        fs::rename(moved.path().join(name), fixture.source.path().join(name)).unwrap();
+    }
+    sync.poll_held_deletions().unwrap();
+    assert!(sync.held_deletions().is_empty());
+
+    // Half of the eight left is allowed once the earlier removals are forgotten
+    remove(&["c", "d", "e", "f"]);
+    assert!(sync.held_deletions().is_empty());
+    assert!(!fixture.claude.path().join("c").exists());
+}
@@ -0,0 +1,112 @@
+use skillsync::{Destination, DestinationType, SkillSync};
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_set_destinations_fills_added_and_optionally_cleans_removed() {
+    let source = TempDir::new().unwrap();
+    let first = TempDir::new().unwrap();
+    let second = TempDir::new().unwrap();
+    let commands = TempDir::new().unwrap();
+
+    let skill = source.path().join("alpha");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(skill.join("SKILL.md"), "Alpha").unwrap();
+
+    let mut sync = SkillSync::new(
+        source.path().to_path_buf(),
+        vec![Destination::new(
+            first.path().to_path_buf(),
+            DestinationType::ClaudeStyle,
+        )],
+    );
+    sync.initial_sync().unwrap();
+    fs::write(first.path().join("notes.txt"), "mine").unwrap();
+
+    // Swapping without cleanup leaves the old destination alone
+    sync.set_destinations(
+        vec![Destination::new(
+            second.path().to_path_buf(),
+            DestinationType::ClaudeStyle,
+        )],
+        false,
+    )
+    .unwrap();
+    assert!(second.path().join("alpha/SKILL.md").exists());
+    assert!(first.path().join("alpha/SKILL.md").exists());
+
+    // With cleanup only synced skills are discarded
+    sync.set_destinations(
+        vec![Destination::new(
+            commands.path().to_path_buf(),
+            DestinationType::GeminiToml,
+        )],
+        true,
+    )
+    .unwrap();
+    assert!(commands.path().join("alpha.toml").exists());
+    assert!(!second.path().join("alpha").exists());
+
+    // Later events only reach the current destinations
+    fs::write(skill.join("SKILL.md"), "Alpha v2").unwrap();
+    sync.handle_batch(&[skill.join("SKILL.md")]);
+    let toml = fs::read_to_string(commands.path().join("alpha.toml")).unwrap();
+    assert!(toml.contains("Alpha v2"));
+    assert_eq!(
+        fs::read_to_string(first.path().join("alpha/SKILL.md")).unwrap(),
+        "Alpha"
+    );
+    assert_eq!(
+        fs::read_to_string(first.path().join("notes.txt")).unwrap(),
+        "mine"
+    );
+}
+
+#[test]
+fn test_failed_destination_change_keeps_current_list() {
+    let source = TempDir::new().unwrap();
+    let first = TempDir::new().unwrap();
+    let blocked = TempDir::new().unwrap();
+
+    let skill = source.path().join("alpha");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(skill.join("SKILL.md"), "Alpha").unwrap();
+    // A file where the new destination directory should go
+    let file = blocked.path().join("skills");
+    fs::write(&file, "").unwrap();
+
+    let current = vec![Destination::new(
+        first.path().to_path_buf(),
+        DestinationType::ClaudeStyle,
+    )];
+    let mut sync = SkillSync::new(source.path().to_path_buf(), current.clone());
+    sync.initial_sync().unwrap();
+
+    let result = sync.set_destinations(
+        vec![Destination::new(file, DestinationType::ClaudeStyle)],
+        true,
+    );
+    assert!(result.is_err());
+    assert_eq!(sync.destinations(), current.as_slice());
+    assert!(first.path().join("alpha/SKILL.md").exists());
+}
+
+#[test]
+fn test_changed_destination_settings_rerender_without_cleaning() {
+    let source = TempDir::new().unwrap();
+    let dest = TempDir::new().unwrap();
+
+    let skill = source.path().join("alpha");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(skill.join("SKILL.md"), "---\ndescription: Alpha\n---\nBody").unwrap();
+
+    let plain = Destination::new(dest.path().to_path_buf(), DestinationType::ClaudeStyle);
+    let mut sync = SkillSync::new(source.path().to_path_buf(), vec![plain.clone()]);
+    sync.initial_sync().unwrap();
+
+    // Only `fields` changed: the same destination, re-rendered and kept
+    let renamed = plain.with_fields([("description".to_string(), "summary".to_string())].into());
+    sync.set_destinations(vec![renamed], true).unwrap();
+    let content = fs::read_to_string(dest.path().join("alpha/SKILL.md")).unwrap();
+    assert!(content.contains("summary: Alpha"));
+}
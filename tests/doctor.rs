@@ -0,0 +1,99 @@
+use skillsync::doctor::{self, Fix, Probes, Severity};
+use skillsync::preflight::{PreflightOutcome, ToolCheck, TOOLS};
+use std::fs;
+use tempfile::TempDir;
+
+/// No tool binary anywhere and no service manager to ask
+fn offline() -> Probes {
+    Probes {
+        tools: |_| PreflightOutcome {
+            tools: TOOLS
+                .iter()
+                .map(|spec| {
+                    let check = ToolCheck {
+                        spec,
+                        binary: None,
+                        version: None,
+                        skills_dirs: Vec::new(),
+                    };
+                    (spec.id, check)
+                })
+                .collect(),
+        },
+        service_loaded: || None,
+    }
+}
+
+#[test]
+fn doctor_reports_and_fixes_missing_directories() {
+    let home = TempDir::new().unwrap();
+    let source = home.path().join(".claude/skills");
+
+    let checks = doctor::diagnose_with(home.path(), &offline());
+    let source_check = checks.iter().find(|c| c.name == "source").unwrap();
+    assert_eq!(source_check.severity, Severity::Warn);
+    assert_eq!(source_check.fix, Some(Fix::CreateDir(source.clone())));
+    assert!(checks
+        .iter()
+        .all(|c| c.severity == Severity::Ok || c.remedy.is_some()));
+    let gemini = checks.iter().find(|c| c.name == "gemini").unwrap();
+    assert_eq!(gemini.severity, Severity::Warn);
+    assert!(gemini.detail.contains("not found"));
+
+    source_check.fix.as_ref().unwrap().apply().unwrap();
+    assert!(source.is_dir());
+    let checks = doctor::diagnose_with(home.path(), &offline());
+    assert_eq!(
+        checks.iter().find(|c| c.name == "source").unwrap().severity,
+        Severity::Ok
+    );
+}
+
+#[test]
+fn doctor_flags_invalid_config_and_stale_daemon_files() {
+    let home = TempDir::new().unwrap();
+    let app_dir = home.path().join("skillsync");
+    let state = app_dir.join("state");
+    fs::create_dir_all(&state).unwrap();
+    fs::write(app_dir.join("config.toml"), "[trash]\nkeep = 3\n").unwrap();
+    fs::write(state.join("skillsync.pid"), "4242\n").unwrap();
+    fs::write(state.join("skillsync.sock"), "").unwrap();
+
+    let checks = doctor::diagnose_with(home.path(), &offline());
+
+    let config = checks.iter().find(|c| c.name == "config").unwrap();
+    assert_eq!(config.severity, Severity::Fail);
+    let lock = checks.iter().find(|c| c.name == "lock").unwrap();
+    assert!(lock.detail.contains("4242"));
+    assert_eq!(
+        lock.fix,
+        Some(Fix::RemoveStale(state.join("skillsync.pid")))
+    );
+    assert!(checks.iter().any(|c| c.name == "socket"));
+}
+
+#[test]
+fn doctor_reinstalls_a_service_for_another_binary() {
+    let home = TempDir::new().unwrap();
+    let service = |home: &TempDir| {
+        doctor::diagnose_with(home.path(), &offline())
+            .into_iter()
+            .find(|c| c.name == "service")
+            .unwrap()
+    };
+    let Some(Fix::InstallService(path)) = service(&home).fix else {
+        // No service manager support on this platform
+        return;
+    };
+
+    Fix::InstallService(path.clone()).apply().unwrap();
+    assert_eq!(service(&home).severity, Severity::Ok);
+
+    let exe = std::env::current_exe().unwrap().display().to_string();
+    let definition = fs::read_to_string(&path).unwrap();
+    fs::write(&path, definition.replace(&exe, "/old/bin/skillsync")).unwrap();
+    let stale = service(&home);
+    assert_eq!(stale.severity, Severity::Warn);
+    assert!(stale.detail.contains("/old/bin/skillsync"));
+    assert_eq!(stale.fix, Some(Fix::InstallService(path)));
+}
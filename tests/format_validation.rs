@@ -92,3 +92,63 @@ Run deployment.
     assert!(out.starts_with("---\n"));
     assert!(out.contains("description: Deploy apps to Vercel"));
 }
+
+#[test]
+fn tool_sections_and_field_maps_shape_each_destination() {
+    let source_dir = TempDir::new().unwrap();
+    let cursor_dir = TempDir::new().unwrap();
+    let codex_dir = TempDir::new().unwrap();
+    let commands_dir = TempDir::new().unwrap();
+
+    let skill_dir = source_dir.path().join("deploy");
+    fs::create_dir_all(&skill_dir).unwrap();
+    let md = "---
+name: deploy
+description: Deploy the app
+allowed-tools: Bash
+cursor:
+  description: Deploy from Cursor
+gemini: { description: \"Deploy, Gemini style\" }
+---
+
+Run the deploy script.
+";
+    fs::write(skill_dir.join("SKILL.md"), md).unwrap();
+
+    let drop_tools = [("allowed-tools".to_string(), String::new())].into();
+    let sync = SkillSync::new(
+        source_dir.path().to_path_buf(),
+        vec![
+            Destination::new(
+                cursor_dir.path().to_path_buf(),
+                DestinationType::ClaudeStyle,
+            )
+            .with_tool("cursor")
+            .with_fields(drop_tools),
+            Destination::new(codex_dir.path().to_path_buf(), DestinationType::ClaudeStyle)
+                .with_tool("codex"),
+            Destination::new(
+                commands_dir.path().to_path_buf(),
+                DestinationType::GeminiToml,
+            )
+            .with_tool("gemini"),
+        ],
+    );
+    sync.initial_sync().unwrap();
+
+    assert_eq!(
+        fs::read_to_string(cursor_dir.path().join("deploy/SKILL.md")).unwrap(),
+        "---\nname: deploy\ndescription: Deploy from Cursor\n---\n\nRun the deploy script.\n"
+    );
+    assert_eq!(
+        fs::read_to_string(codex_dir.path().join("deploy/SKILL.md")).unwrap(),
+        "---\nname: deploy\ndescription: Deploy the app\nallowed-tools: Bash\n---\n\nRun the deploy script.\n"
+    );
+    let value: toml::Value =
+        toml::from_str(&fs::read_to_string(commands_dir.path().join("deploy.toml")).unwrap())
+            .unwrap();
+    assert_eq!(
+        value.get("description").and_then(|v| v.as_str()),
+        Some("Deploy, Gemini style")
+    );
+}
@@ -0,0 +1,142 @@
+use skillsync::import::{self, ImportAction, ImportFormat};
+use skillsync::trash::{Mirror, Trash};
+use std::fs;
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[test]
+fn cursor_rules_import_as_skill_directories() {
+    let rules_dir = TempDir::new().unwrap();
+    let source_dir = TempDir::new().unwrap();
+
+    fs::write(
+        rules_dir.path().join("Rust Style.mdc"),
+        "---\ndescription: Rust conventions\nglobs: \"*.rs\"\nalwaysApply: false\n---\n\nUse anyhow.\n",
+    )
+    .unwrap();
+    fs::write(rules_dir.path().join("notes.txt"), "ignored").unwrap();
+
+    let skills = import::discover(ImportFormat::CursorRules, rules_dir.path()).unwrap();
+    assert_eq!(skills.len(), 1);
+    assert_eq!(skills[0].name, "rust-style");
+
+    let plan = import::plan(ImportFormat::CursorRules, skills, source_dir.path(), false);
+    assert_eq!(plan.entries[0].action, ImportAction::Create);
+    assert_eq!(import::apply(&plan, None).unwrap(), 1);
+
+    let skill_md =
+        fs::read_to_string(source_dir.path().join("rust-style").join("SKILL.md")).unwrap();
+    assert!(skill_md.starts_with("---\nname: rust-style\ndescription: Rust conventions\n"));
+    assert!(skill_md.contains("  imported-from: cursor\n"));
+    assert!(skill_md.contains("  globs: \"*.rs\"\n"));
+    assert!(skill_md.contains("  always-apply: false\n"));
+    assert!(skill_md.ends_with("---\n\nUse anyhow.\n"));
+}
+
+#[test]
+fn import_refuses_to_overwrite_without_force() {
+    let prompts_dir = TempDir::new().unwrap();
+    let source_dir = TempDir::new().unwrap();
+
+    fs::write(
+        prompts_dir.path().join("review.prompt.md"),
+        "---\ndescription: Review a PR\nmode: agent\ntools:\n  - codebase\n  - 'fetch'\n---\nReview ${input:pr}.\n",
+    )
+    .unwrap();
+    let existing = source_dir.path().join("review");
+    fs::create_dir_all(&existing).unwrap();
+    fs::write(existing.join("SKILL.md"), "original").unwrap();
+    fs::write(existing.join("notes.md"), "mine").unwrap();
+
+    let skills = import::discover(ImportFormat::CopilotPrompts, prompts_dir.path()).unwrap();
+    let plan = import::plan(
+        ImportFormat::CopilotPrompts,
+        skills.clone(),
+        source_dir.path(),
+        false,
+    );
+    assert_eq!(plan.entries[0].action, ImportAction::SkipExisting);
+    assert_eq!(import::apply(&plan, None).unwrap(), 0);
+    assert_eq!(
+        fs::read_to_string(existing.join("SKILL.md")).unwrap(),
+        "original"
+    );
+
+    let plan = import::plan(
+        ImportFormat::CopilotPrompts,
+        skills,
+        source_dir.path(),
+        true,
+    );
+    assert_eq!(plan.entries[0].action, ImportAction::Overwrite);
+    let state_dir = TempDir::new().unwrap();
+    let trash = Trash::new(
+        state_dir.path().to_path_buf(),
+        10,
+        Duration::from_secs(3600),
+    );
+    assert_eq!(import::apply(&plan, Some(&trash)).unwrap(), 1);
+    let skill_md = fs::read_to_string(existing.join("SKILL.md")).unwrap();
+    assert!(skill_md.contains("description: Review a PR"));
+    assert!(skill_md.contains("  mode: agent\n"));
+    assert!(skill_md.contains("  tools: \"[codebase, fetch]\"\n"));
+
+    // The replaced skill went to the trash whole
+    assert!(!existing.join("notes.md").exists());
+    let snapshot = trash.snapshot("latest").unwrap();
+    assert_eq!(snapshot.entries, vec![existing.clone()]);
+    fs::remove_dir_all(&existing).unwrap();
+    let mirrors = [Mirror {
+        source: source_dir.path().to_path_buf(),
+        destinations: Vec::new(),
+    }];
+    trash.restore(&snapshot, Some("review"), &mirrors).unwrap();
+    assert_eq!(
+        fs::read_to_string(existing.join("notes.md")).unwrap(),
+        "mine"
+    );
+}
+
+#[test]
+fn codex_prompt_without_frontmatter_uses_first_line_as_description() {
+    let prompts_dir = TempDir::new().unwrap();
+    fs::write(
+        prompts_dir.path().join("commit.md"),
+        "Write a commit message for $ARGUMENTS.\n\nKeep it short.\n",
+    )
+    .unwrap();
+
+    let skills = import::discover(ImportFormat::CodexPrompts, prompts_dir.path()).unwrap();
+    assert_eq!(
+        skills[0].description.as_deref(),
+        Some("Write a commit message for $ARGUMENTS.")
+    );
+}
+
+#[test]
+fn files_with_the_same_skill_name_import_once() {
+    let rules_dir = TempDir::new().unwrap();
+    let source_dir = TempDir::new().unwrap();
+
+    fs::write(
+        rules_dir.path().join("My Rule.mdc"),
+        "---\ndescription: First\n---\n\nOne.\n",
+    )
+    .unwrap();
+    fs::write(
+        rules_dir.path().join("my-rule.mdc"),
+        "---\ndescription: Second\n---\n\nTwo.\n",
+    )
+    .unwrap();
+
+    let skills = import::discover(ImportFormat::CursorRules, rules_dir.path()).unwrap();
+    let plan = import::plan(ImportFormat::CursorRules, skills, source_dir.path(), true);
+    let actions: Vec<_> = plan.entries.iter().map(|entry| entry.action).collect();
+    assert_eq!(
+        actions,
+        vec![ImportAction::Create, ImportAction::SkipDuplicate]
+    );
+    assert_eq!(import::apply(&plan, None).unwrap(), 1);
+    let skill_md = fs::read_to_string(source_dir.path().join("my-rule/SKILL.md")).unwrap();
+    assert!(skill_md.contains("description: First"));
+}
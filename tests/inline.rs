@@ -0,0 +1,114 @@
+use skillsync::inline::InlineSettings;
+use skillsync::{Destination, DestinationType, SkillSync};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+mod common;
+use common::write;
+
+fn prompt(commands: &Path, skill: &str) -> String {
+    let text = fs::read_to_string(commands.join(format!("{}.toml", skill))).unwrap();
+    let value: toml::Value = toml::from_str(&text).expect("valid TOML");
+    value["prompt"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn referenced_files_are_inlined_into_toml_prompts() {
+    let source = TempDir::new().unwrap();
+    let commands = TempDir::new().unwrap();
+    let plain = TempDir::new().unwrap();
+    let skill = source.path().join("api");
+    write(
+        &skill.join("SKILL.md"),
+        "---\ndescription: Call the API\n---\n\n@include parts/intro.md\n\
+         See [the reference](references/api.md) and [the script](scripts/run.py).\n\
+         Also [big](data/big.txt) and [quotes](notes/quotes.md).\n",
+    );
+    write(&skill.join("parts/intro.md"), "Intro.\n@include loop.md\n");
+    write(&skill.join("parts/loop.md"), "Loop.\n@include intro.md\n");
+    write(
+        &skill.join("references/api.md"),
+        "GET /items\nBack to [skill](../SKILL.md), [intro](../parts/intro.md).\n",
+    );
+    write(&skill.join("scripts/run.py"), "print('hi')\n");
+    write(&skill.join("data/big.txt"), &"x".repeat(2048));
+    write(&skill.join("notes/quotes.md"), "a ''' b\n");
+
+    let settings = InlineSettings {
+        max_file_bytes: 1024,
+        ..Default::default()
+    };
+    let sync = SkillSync::new(
+        source.path().to_path_buf(),
+        vec![
+            Destination::new(commands.path().to_path_buf(), DestinationType::GeminiToml)
+                .with_inline(settings),
+            Destination::new(plain.path().to_path_buf(), DestinationType::GeminiToml),
+        ],
+    );
+    sync.initial_sync().unwrap();
+
+    let inlined = prompt(commands.path(), "api");
+    assert!(inlined.starts_with(
+        "Intro.\nLoop.\n<!-- skillsync: intro.md not inlined: include cycle -->\nSee [the reference]"
+    ));
+    assert!(inlined.contains("# Referenced files\n\n## references/api.md\n\nGET /items\n"));
+    assert!(inlined.contains("## scripts/run.py\n\n```py\nprint('hi')\n```"));
+    assert!(inlined.contains("data/big.txt not inlined: 2048 bytes exceeds max_file_bytes (1024)"));
+    assert!(inlined.contains("notes/quotes.md not inlined: contains a triple single quote"));
+    // Files already inlined by @include are not appended again
+    assert!(!inlined.contains("## parts/intro.md"));
+
+    // Destinations without inlining keep the plain body
+    assert!(!prompt(plain.path(), "api").contains("Referenced files"));
+
+    // Changing a referenced file re-renders the command
+    write(&skill.join("references/api.md"), "GET /v2/items\n");
+    sync.handle_batch(&[skill.join("references/api.md")]);
+    assert!(prompt(commands.path(), "api").contains("GET /v2/items"));
+
+    write(&skill.join("scripts/run.py"), "print('bye')\n");
+    assert_eq!(sync.reconcile().unwrap(), 1);
+    assert!(prompt(commands.path(), "api").contains("print('bye')"));
+}
+
+#[test]
+fn links_in_inlined_files_point_at_their_own_directory() {
+    let source = TempDir::new().unwrap();
+    let commands = TempDir::new().unwrap();
+    let skill = source.path().join("api");
+    write(
+        &skill.join("SKILL.md"),
+        "---\ndescription: Call the API\n---\n\n@include parts/intro.md\nSee [the reference](references/api.md).\n",
+    );
+    write(
+        &skill.join("parts/intro.md"),
+        "Read [setup](setup.md) first.\n",
+    );
+    write(&skill.join("parts/setup.md"), "Setup.\n");
+    write(
+        &skill.join("references/api.md"),
+        "GET /items, see [errors](errors.txt).\n",
+    );
+    write(&skill.join("references/errors.txt"), "404\n");
+
+    let sync = SkillSync::new(
+        source.path().to_path_buf(),
+        vec![
+            Destination::new(commands.path().to_path_buf(), DestinationType::GeminiToml)
+                .with_inline(InlineSettings::default())
+                .with_path_rewriting(),
+        ],
+    );
+    sync.initial_sync().unwrap();
+
+    let inlined = prompt(commands.path(), "api");
+    let dir = skill.display();
+    assert!(inlined.contains(&format!("Read [setup]({}/parts/setup.md) first.", dir)));
+    assert!(inlined.contains(&format!("[the reference]({}/references/api.md)", dir)));
+    assert!(inlined.contains(&format!("see [errors]({}/references/errors.txt)", dir)));
+    // Both linked files were found relative to the file linking them
+    assert!(inlined.contains("## parts/setup.md\n\nSetup."));
+    assert!(inlined.contains("## references/errors.txt\n\n```txt\n404\n```"));
+}
@@ -0,0 +1,28 @@
+use skillsync::instance::{InstanceLock, LockState};
+use tempfile::TempDir;
+
+#[test]
+fn second_instance_sees_holder_pid() {
+    let dir = TempDir::new().unwrap();
+    let lock_file = dir.path().join("state").join("skillsync.pid");
+
+    let first = match InstanceLock::acquire(&lock_file).unwrap() {
+        LockState::Acquired(lock) => lock,
+        LockState::Held(pid) => panic!("lock unexpectedly held by {:?}", pid),
+    };
+    assert_eq!(
+        std::fs::read_to_string(&lock_file).unwrap().trim(),
+        std::process::id().to_string()
+    );
+
+    match InstanceLock::acquire(&lock_file).unwrap() {
+        LockState::Held(pid) => assert_eq!(pid, Some(std::process::id())),
+        LockState::Acquired(_) => panic!("lock acquired twice"),
+    }
+
+    drop(first);
+    assert!(matches!(
+        InstanceLock::acquire(&lock_file).unwrap(),
+        LockState::Acquired(_)
+    ));
+}
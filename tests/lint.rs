@@ -0,0 +1,182 @@
+use skillsync::inline::InlineSettings;
+use skillsync::lint::{self, Level};
+use skillsync::{Destination, DestinationType};
+use tempfile::TempDir;
+
+mod common;
+use common::write;
+
+fn rules(report: &lint::LintReport, skill: &str) -> Vec<&'static str> {
+    report
+        .findings
+        .iter()
+        .filter(|f| f.skill == skill)
+        .map(|f| f.rule)
+        .collect()
+}
+
+#[test]
+fn lint_accepts_well_formed_skill() {
+    let source = TempDir::new().unwrap();
+    write(
+        &source.path().join("pdf-tools/SKILL.md"),
+        "---\nname: pdf-tools\ndescription: Fill PDF forms\n---\n\nSee [forms](references/forms.md#fields).\n",
+    );
+    write(
+        &source.path().join("pdf-tools/references/forms.md"),
+        "# Forms\n",
+    );
+
+    let dest = Destination::new(source.path().join("out"), DestinationType::GeminiToml);
+    let report = lint::lint_source(source.path(), &[dest]).unwrap();
+
+    assert_eq!(report.skills, 1);
+    assert!(report.findings.is_empty(), "{:?}", report.findings);
+}
+
+#[test]
+fn lint_reports_each_rule() {
+    let source = TempDir::new().unwrap();
+    write(&source.path().join("no-skill-md/notes.md"), "notes\n");
+    write(&source.path().join("bare/SKILL.md"), "# No frontmatter\n");
+    write(
+        &source.path().join("renamed/SKILL.md"),
+        "---\nname: Old_Name\n---\n\n[missing](scripts/run.sh)\n",
+    );
+    write(
+        &source.path().join("quotes/SKILL.md"),
+        "---\nname: quotes\ndescription: Breaks TOML\n---\n\nBody with ''' inside\n",
+    );
+    write(&source.path().join("README.md"), "stray\n");
+
+    let gemini = Destination::new(source.path().join("out"), DestinationType::GeminiToml);
+    let report = lint::lint_source(source.path(), &[gemini]).unwrap();
+
+    assert_eq!(report.skills, 4);
+    assert_eq!(rules(&report, "no-skill-md"), vec!["skill-md-missing"]);
+    assert_eq!(rules(&report, "bare"), vec!["frontmatter-missing"]);
+    assert_eq!(
+        rules(&report, "renamed"),
+        vec![
+            "name-mismatch",
+            "name-format",
+            "description-missing",
+            "broken-link"
+        ]
+    );
+    assert_eq!(rules(&report, "quotes"), vec!["toml-invalid"]);
+    assert_eq!(rules(&report, "README.md"), vec!["stray-file"]);
+    assert_eq!(report.count(Level::Warning), 2);
+
+    // Without a Gemini TOML destination the ''' body is fine
+    let claude = Destination::new(source.path().join("out"), DestinationType::ClaudeStyle);
+    let report = lint::lint_source(source.path(), &[claude]).unwrap();
+    assert!(rules(&report, "quotes").is_empty());
+}
+
+#[test]
+fn lint_warns_about_unknown_target_tools() {
+    let source = TempDir::new().unwrap();
+    write(
+        &source.path().join("review/SKILL.md"),
+        "---\nname: review\ndescription: Review\nskillsync: { only: [codex, vscode] }\n---\n\nBody\n",
+    );
+
+    let report = lint::lint_source(source.path(), &[]).unwrap();
+    assert_eq!(rules(&report, "review"), vec!["target-unknown"]);
+    assert_eq!(report.count(Level::Warning), 1);
+    assert!(report.findings[0].message.contains("'vscode'"));
+}
+
+#[test]
+fn lint_checks_templates_for_each_destination() {
+    let source = TempDir::new().unwrap();
+    write(
+        &source.path().join("deploy/SKILL.md"),
+        "---\nname: deploy\ndescription: Deploy\nskillsync: { template: true }\n---\n\n\
+         {% if tool == \"gemini\" %}\n{{ tool.version }}\n{% endif %}\n",
+    );
+
+    let codex = Destination::new(source.path().join("codex"), DestinationType::ClaudeStyle)
+        .with_tool("codex");
+    let gemini = Destination::new(source.path().join("gemini"), DestinationType::ClaudeStyle)
+        .with_tool("gemini");
+    let report = lint::lint_source(source.path(), &[codex.clone(), gemini]).unwrap();
+
+    // Unknown variables are errors even in branches a destination skips
+    assert_eq!(rules(&report, "deploy"), vec!["template-invalid"]);
+    assert!(report.findings[0].message.starts_with("line 8:"));
+    let report = lint::lint_source(source.path(), &[codex]).unwrap();
+    assert_eq!(rules(&report, "deploy"), vec!["template-invalid"]);
+}
+
+#[test]
+fn lint_checks_toml_for_each_gemini_destination() {
+    let source = TempDir::new().unwrap();
+    write(
+        &source.path().join("colors/SKILL.md"),
+        "---\nname: colors\ndescription: Colors\n---\n\nSee [sample](sample.txt).\n",
+    );
+    // Terminal escapes are fine in Markdown but not in a TOML string
+    write(
+        &source.path().join("colors/sample.txt"),
+        "\x1b[31mred\x1b[0m\n",
+    );
+
+    let plain = Destination::new(source.path().join("plain"), DestinationType::GeminiToml);
+    let inlined = Destination::new(source.path().join("inlined"), DestinationType::GeminiToml)
+        .with_inline(InlineSettings::default());
+    let report = lint::lint_source(source.path(), std::slice::from_ref(&plain)).unwrap();
+    assert!(rules(&report, "colors").is_empty());
+    let report = lint::lint_source(source.path(), &[plain, inlined]).unwrap();
+    assert_eq!(rules(&report, "colors"), vec!["toml-invalid"]);
+}
+
+#[test]
+fn lint_checks_only_the_winning_copy_of_each_skill() {
+    let personal = TempDir::new().unwrap();
+    let team = TempDir::new().unwrap();
+    write(
+        &personal.path().join("review/SKILL.md"),
+        "---\nname: review\ndescription: Review\n---\n\nBody\n",
+    );
+    // Hidden by the personal copy, so never synced
+    write(&team.path().join("review/notes.md"), "no SKILL.md\n");
+    write(&team.path().join("deploy/notes.md"), "no SKILL.md\n");
+
+    let sources = [personal.path().to_path_buf(), team.path().to_path_buf()];
+    let report = lint::lint_sources(&sources, &[], personal.path()).unwrap();
+    assert_eq!(report.skills, 2);
+    assert!(rules(&report, "review").is_empty());
+    assert_eq!(rules(&report, "deploy"), vec!["skill-md-missing"]);
+
+    // A source that is not checked out yet does not stop the others
+    let missing = team.path().join("not-cloned");
+    let sources = [missing.clone(), personal.path().to_path_buf()];
+    let report = lint::lint_sources(&sources, &[], personal.path()).unwrap();
+    assert_eq!(report.skills, 1);
+    assert_eq!(rules(&report, ""), vec!["source-missing"]);
+    assert_eq!(report.findings[0].path, missing);
+}
+
+#[test]
+fn lint_checks_toml_of_the_rendered_template() {
+    let source = TempDir::new().unwrap();
+    write(
+        &source.path().join("notes/SKILL.md"),
+        "---\nname: notes\ndescription: Notes\nskillsync: { template: true }\n---\n\n\
+         {% if tool == \"codex\" %}\nWrap code in ''' fences.\n{% endif %}\n\
+         Notes live in {{ home }}/notes and {{ dest.path }}.\n",
+    );
+
+    // The ''' line only reaches Codex, never the Gemini command
+    let gemini = Destination::new(source.path().join("commands"), DestinationType::GeminiToml)
+        .with_tool("gemini");
+    let report = lint::lint_source(source.path(), std::slice::from_ref(&gemini)).unwrap();
+    assert!(rules(&report, "notes").is_empty(), "{:?}", report.findings);
+
+    let codex = Destination::new(source.path().join("codex"), DestinationType::GeminiToml)
+        .with_tool("codex");
+    let report = lint::lint_source(source.path(), &[gemini, codex]).unwrap();
+    assert_eq!(rules(&report, "notes"), vec!["toml-invalid"]);
+}
@@ -0,0 +1,144 @@
+use skillsync::{project, Config};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+mod common;
+use common::{write, write_skill};
+
+fn skill(root: &Path, name: &str) {
+    write_skill(&project::skills_dir(root), name);
+}
+
+fn config(home: &Path, projects: &str) -> Config {
+    write(&home.join("skillsync/config.toml"), projects);
+    Config::for_home(home).expect("Failed to load config")
+}
+
+#[test]
+fn projects_are_discovered_under_workspaces_and_listed_paths() {
+    let home = TempDir::new().unwrap();
+    let src = home.path().join("src");
+    skill(&src.join("app"), "build");
+    skill(&src.join("org/nested"), "test");
+    skill(&src.join("org/deep/er/too-deep"), "x");
+    skill(&src.join(".hidden"), "x");
+    // Repositories end the search, with or without skills
+    fs::create_dir_all(src.join("repo/.git")).unwrap();
+    skill(&src.join("repo/vendored"), "x");
+    let listed = home.path().join("elsewhere");
+    fs::create_dir_all(&listed).unwrap();
+
+    let config = config(
+        home.path(),
+        "[projects]\nworkspaces = [\"~/src\"]\npaths = [\"~/elsewhere\", \"~/missing\"]\n",
+    );
+    assert_eq!(
+        project::discover(&config.projects),
+        BTreeSet::from([src.join("app"), src.join("org/nested"), listed])
+    );
+}
+
+#[test]
+fn project_skills_are_mirrored_inside_the_project() {
+    let home = TempDir::new().unwrap();
+    let repo = home.path().join("src/app");
+    skill(&repo, "build");
+    skill(&repo, "deploy");
+    write(
+        &repo.join(".gemini/commands/by-hand.toml"),
+        "prompt = 'mine'\n",
+    );
+    fs::create_dir_all(repo.join(".cursor/skills/by-hand")).unwrap();
+
+    let config = config(
+        home.path(),
+        "[trash]\nenabled = false\n\n[projects]\nworkspaces = [\"~/src\"]\n",
+    );
+    let sync = project::project_sync(&config, &repo, &config.projects.destinations);
+    sync.initial_sync().unwrap();
+
+    assert!(repo.join(".gemini/commands/build.toml").exists());
+    assert!(repo.join(".codex/skills/deploy/SKILL.md").exists());
+    assert!(repo.join(".cursor/skills/build/SKILL.md").exists());
+    let manifest = project::manifest_file(&config, &repo);
+    assert!(manifest.starts_with(config.state_dir.join("projects")));
+    assert!(manifest.exists());
+    // Roots that differ only in where `/` and `__` sit keep separate manifests
+    assert_ne!(
+        project::manifest_file(&config, Path::new("/a/b__c")),
+        project::manifest_file(&config, Path::new("/a__b/c"))
+    );
+
+    // Only what skillsync wrote is ever cleaned up
+    fs::remove_dir_all(project::skills_dir(&repo).join("deploy")).unwrap();
+    assert_eq!(sync.reconcile().unwrap(), 4);
+    assert!(!repo.join(".gemini/commands/deploy.toml").exists());
+    assert!(!repo.join(".codex/skills/deploy").exists());
+    assert!(repo.join(".gemini/commands/by-hand.toml").exists());
+    assert!(repo.join(".cursor/skills/by-hand").exists());
+
+    // The manifest outlives the daemon: a skill removed while it was not
+    // running is still recognised as skillsync's own
+    fs::remove_dir_all(project::skills_dir(&repo).join("build")).unwrap();
+    let restarted = project::project_sync(&config, &repo, &config.projects.destinations);
+    restarted.initial_sync().unwrap();
+    assert!(!repo.join(".gemini/commands/build.toml").exists());
+    assert!(repo.join(".gemini/commands/by-hand.toml").exists());
+}
+
+#[test]
+fn project_entries_skillsync_did_not_write_are_never_touched() {
+    let home = TempDir::new().unwrap();
+    let repo = home.path().join("src/app");
+    skill(&repo, "build");
+    write(
+        &repo.join(".gemini/commands/build.toml"),
+        "prompt = 'mine'\n",
+    );
+    write(&repo.join(".codex/skills/build/notes.md"), "mine\n");
+
+    let config = config(
+        home.path(),
+        "[trash]\nenabled = false\n\n[projects]\nworkspaces = [\"~/src\"]\n",
+    );
+    let sync = project::project_sync(&config, &repo, &config.projects.destinations);
+    sync.initial_sync().unwrap();
+
+    // Same-named entries are skipped, the rest are written
+    assert_eq!(
+        fs::read_to_string(repo.join(".gemini/commands/build.toml")).unwrap(),
+        "prompt = 'mine'\n"
+    );
+    assert!(!repo.join(".codex/skills/build/SKILL.md").exists());
+    assert!(repo.join(".cursor/skills/build/SKILL.md").exists());
+
+    // Removing the skill only removes what skillsync created
+    fs::remove_dir_all(project::skills_dir(&repo).join("build")).unwrap();
+    sync.reconcile().unwrap();
+    assert!(!repo.join(".cursor/skills/build").exists());
+    assert!(repo.join(".gemini/commands/build.toml").exists());
+    assert!(repo.join(".codex/skills/build/notes.md").exists());
+}
+
+#[test]
+fn project_mass_deletion_is_held() {
+    let home = TempDir::new().unwrap();
+    let repo = home.path().join("src/app");
+    for name in ["a", "b", "c", "d"] {
+        skill(&repo, name);
+    }
+
+    let config = config(home.path(), "[projects]\nworkspaces = [\"~/src\"]\n");
+    let sync = project::project_sync(&config, &repo, &config.projects.destinations);
+    sync.initial_sync().unwrap();
+
+    // A branch switch that drops most of the project's skills
+    for name in ["a", "b", "c"] {
+        fs::remove_dir_all(project::skills_dir(&repo).join(name)).unwrap();
+    }
+    sync.reconcile().unwrap();
+    assert!(repo.join(".codex/skills/a/SKILL.md").exists());
+    assert_eq!(sync.held_deletions(), vec!["a", "b", "c"]);
+}
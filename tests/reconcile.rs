@@ -0,0 +1,49 @@
+use skillsync::{Destination, DestinationType, SkillSync};
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn reconcile_repairs_only_drifted_outputs() {
+    let source = TempDir::new().unwrap();
+    let claude = TempDir::new().unwrap();
+    let toml = TempDir::new().unwrap();
+    let sync = SkillSync::new(
+        source.path().to_path_buf(),
+        vec![
+            Destination::new(claude.path().to_path_buf(), DestinationType::ClaudeStyle),
+            Destination::new(toml.path().to_path_buf(), DestinationType::GeminiToml),
+        ],
+    );
+
+    for name in ["alpha", "beta"] {
+        let dir = source.path().join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!("---\ndescription: {}\n---\nBody", name),
+        )
+        .unwrap();
+    }
+    sync.initial_sync().unwrap();
+    assert_eq!(sync.reconcile().unwrap(), 0);
+
+    // Drift: a stale copy, a missing TOML and an orphan left behind
+    fs::write(
+        claude.path().join("alpha").join("SKILL.md"),
+        "edited by hand",
+    )
+    .unwrap();
+    fs::remove_file(toml.path().join("beta.toml")).unwrap();
+    fs::create_dir_all(claude.path().join("stray")).unwrap();
+
+    assert_eq!(sync.reconcile().unwrap(), 3);
+    assert!(
+        fs::read_to_string(claude.path().join("alpha").join("SKILL.md"))
+            .unwrap()
+            .contains("description: alpha")
+    );
+    assert!(toml.path().join("beta.toml").exists());
+    assert!(!claude.path().join("stray").exists());
+
+    assert_eq!(sync.reconcile().unwrap(), 0);
+}
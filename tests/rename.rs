@@ -0,0 +1,87 @@
+mod common;
+
+use common::{write, Fixture};
+use std::fs;
+use std::path::PathBuf;
+
+/// A fixture whose source holds `old-name` with a script
+fn fixture() -> Fixture {
+    let fixture = Fixture::new();
+    let skill = fixture.source.path().join("old-name");
+    write(
+        &skill.join("SKILL.md"),
+        "---\ndescription: Renamed\n---\nBody",
+    );
+    write(&skill.join("scripts").join("run.sh"), "echo hi");
+    fixture
+}
+
+fn rename_events(fixture: &Fixture) -> Vec<PathBuf> {
+    let src = fixture.source.path();
+    vec![
+        src.join("old-name"),
+        src.join("new-name"),
+        src.join("new-name").join("SKILL.md"),
+    ]
+}
+
+fn assert_renamed(fixture: &Fixture) {
+    let claude = fixture.claude.path();
+    assert!(!claude.join("old-name").exists());
+    assert!(claude.join("new-name").join("SKILL.md").exists());
+    assert!(claude
+        .join("new-name")
+        .join("scripts")
+        .join("run.sh")
+        .exists());
+    assert!(!fixture.toml.path().join("old-name.toml").exists());
+    assert!(fixture.toml.path().join("new-name.toml").exists());
+    // Nothing went through the trash, so nothing was ever missing
+    assert!(fixture.trash().snapshots().unwrap().is_empty());
+}
+
+#[test]
+fn directory_rename_moves_destinations_in_place() {
+    let fixture = fixture();
+    let sync = fixture.sync().with_trash(fixture.trash());
+    sync.initial_sync().unwrap();
+
+    let src = fixture.source.path();
+    fs::rename(src.join("old-name"), src.join("new-name")).unwrap();
+    sync.handle_batch(&rename_events(&fixture));
+
+    assert_renamed(&fixture);
+}
+
+#[test]
+fn rename_without_known_inode_matches_by_content() {
+    let fixture = fixture();
+    fixture
+        .sync()
+        .with_trash(fixture.trash())
+        .initial_sync()
+        .unwrap();
+
+    // A fresh instance has no recorded inodes, and a copy gets a new one anyway
+    let src = fixture.source.path();
+    let new_dir = src.join("new-name");
+    fs::create_dir_all(new_dir.join("scripts")).unwrap();
+    fs::copy(
+        src.join("old-name").join("SKILL.md"),
+        new_dir.join("SKILL.md"),
+    )
+    .unwrap();
+    fs::copy(
+        src.join("old-name").join("scripts").join("run.sh"),
+        new_dir.join("scripts").join("run.sh"),
+    )
+    .unwrap();
+    fs::remove_dir_all(src.join("old-name")).unwrap();
+
+    fixture
+        .sync()
+        .with_trash(fixture.trash())
+        .handle_batch(&rename_events(&fixture));
+
+    assert_renamed(&fixture);
+}
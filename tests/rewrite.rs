@@ -0,0 +1,59 @@
+use skillsync::{Destination, DestinationType, SkillSync};
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+use common::write;
+
+#[test]
+fn skill_paths_are_rewritten_per_destination() {
+    let home = TempDir::new().unwrap();
+    let source = home.path().join(".claude/skills");
+    let codex = home.path().join(".codex/skills");
+    let verbatim = home.path().join(".other/skills");
+    let commands = home.path().join(".gemini/commands");
+    write(
+        &source.join("foo/SKILL.md"),
+        "---\ndescription: Run foo\n---\n\nRun `~/.claude/skills/foo/scripts/run.sh`.\n\
+         Data lives in ${CLAUDE_SKILL_DIR}/data.\nSee [usage](references/usage.md).\n",
+    );
+    write(
+        &source.join("foo/scripts/run.sh"),
+        "#!/bin/sh\ncat $HOME/.claude/skills/foo/data/x\n",
+    );
+    write(&source.join("foo/references/usage.md"), "Usage.\n");
+
+    let sync = SkillSync::new(
+        source.clone(),
+        vec![
+            Destination::new(codex.clone(), DestinationType::ClaudeStyle).with_path_rewriting(),
+            Destination::new(verbatim.clone(), DestinationType::ClaudeStyle),
+            Destination::new(commands.clone(), DestinationType::GeminiToml).with_path_rewriting(),
+        ],
+    )
+    .with_home(home.path().to_path_buf());
+    sync.initial_sync().unwrap();
+
+    let skill_md = fs::read_to_string(codex.join("foo/SKILL.md")).unwrap();
+    assert!(skill_md.contains("Run `~/.codex/skills/foo/scripts/run.sh`."));
+    assert!(skill_md.contains(&format!(
+        "Data lives in {}/data.",
+        codex.join("foo").display()
+    )));
+    assert!(skill_md.contains("[usage](references/usage.md)"));
+    let script = fs::read_to_string(codex.join("foo/scripts/run.sh")).unwrap();
+    assert!(script.contains("cat $HOME/.codex/skills/foo/data/x"));
+
+    // Destinations without rewrite_paths keep the source text
+    let untouched = fs::read_to_string(verbatim.join("foo/SKILL.md")).unwrap();
+    assert!(untouched.contains("~/.claude/skills/foo/scripts/run.sh"));
+    assert!(untouched.contains("${CLAUDE_SKILL_DIR}/data"));
+
+    // Flattened prompts point at the source skill
+    let text = fs::read_to_string(commands.join("foo.toml")).unwrap();
+    let value: toml::Value = toml::from_str(&text).unwrap();
+    let prompt = value["prompt"].as_str().unwrap();
+    let skill_dir = source.join("foo").display().to_string();
+    assert!(prompt.contains(&format!("Data lives in {}/data.", skill_dir)));
+    assert!(prompt.contains(&format!("[usage]({}/references/usage.md)", skill_dir)));
+}
@@ -0,0 +1,109 @@
+use skillsync::{Destination, DestinationType, SkillSync};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn write_skill(root: &Path, name: &str, description: &str, extra: &[&str]) {
+    let dir = root.join(name);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("SKILL.md"),
+        format!("---\ndescription: {}\n---\nBody", description),
+    )
+    .unwrap();
+    for file in extra {
+        fs::write(dir.join(file), description).unwrap();
+    }
+}
+
+fn description(claude: &Path, skill: &str) -> String {
+    fs::read_to_string(claude.join(skill).join("SKILL.md")).unwrap()
+}
+
+#[test]
+fn higher_priority_source_overrides_by_name() {
+    let personal = TempDir::new().unwrap();
+    let team = TempDir::new().unwrap();
+    let claude = TempDir::new().unwrap();
+    let toml = TempDir::new().unwrap();
+    let sync = SkillSync::new(
+        personal.path().to_path_buf(),
+        vec![
+            Destination::new(claude.path().to_path_buf(), DestinationType::ClaudeStyle),
+            Destination::new(toml.path().to_path_buf(), DestinationType::GeminiToml),
+        ],
+    )
+    .with_sources(vec![
+        personal.path().to_path_buf(),
+        team.path().to_path_buf(),
+    ]);
+
+    write_skill(team.path(), "review", "team review", &["team-notes.md"]);
+    write_skill(team.path(), "deploy", "team deploy", &[]);
+    write_skill(personal.path(), "review", "my review", &[]);
+    write_skill(personal.path(), "scratch", "mine", &[]);
+    sync.initial_sync().unwrap();
+
+    assert!(description(claude.path(), "review").contains("my review"));
+    // The whole directory is overridden, not merged file by file
+    assert!(!claude.path().join("review/team-notes.md").exists());
+    assert!(description(claude.path(), "deploy").contains("team deploy"));
+    assert!(description(claude.path(), "scratch").contains("mine"));
+    let rendered = fs::read_to_string(toml.path().join("review.toml")).unwrap();
+    assert!(rendered.contains("my review"));
+    assert_eq!(sync.reconcile().unwrap(), 0);
+
+    // Edits to a hidden skill do not leak through
+    fs::write(
+        team.path().join("review/SKILL.md"),
+        "---\ndescription: x\n---\n",
+    )
+    .unwrap();
+    sync.handle_batch(&[team.path().join("review/SKILL.md")]);
+    assert!(description(claude.path(), "review").contains("my review"));
+
+    // Dropping the override falls back to the team skill, extra files included
+    fs::remove_dir_all(personal.path().join("review")).unwrap();
+    sync.handle_batch(&[personal.path().join("review")]);
+    assert!(description(claude.path(), "review").contains("description: x"));
+    assert!(claude.path().join("review/team-notes.md").exists());
+
+    // A skill no source provides any more is an orphan
+    fs::remove_dir_all(team.path().join("deploy")).unwrap();
+    assert_eq!(sync.reconcile().unwrap(), 2);
+    assert!(!claude.path().join("deploy").exists());
+    assert!(!toml.path().join("deploy.toml").exists());
+}
+
+#[test]
+fn missing_source_does_not_orphan_its_skills() {
+    let parent = TempDir::new().unwrap();
+    let personal = parent.path().join("personal");
+    let team = parent.path().join("team");
+    let claude = TempDir::new().unwrap();
+    write_skill(&personal, "scratch", "mine", &[]);
+    write_skill(&team, "deploy", "team deploy", &[]);
+
+    let sync = SkillSync::new(
+        personal.clone(),
+        vec![Destination::new(
+            claude.path().to_path_buf(),
+            DestinationType::ClaudeStyle,
+        )],
+    )
+    .with_sources(vec![personal.clone(), team.clone()]);
+    sync.initial_sync().unwrap();
+    assert!(claude.path().join("deploy").exists());
+
+    // The team checkout disappears, e.g. while being re-cloned
+    fs::remove_dir_all(&team).unwrap();
+    sync.handle_batch(&[team.clone(), team.join("deploy")]);
+    assert_eq!(sync.reconcile().unwrap(), 0);
+    sync.initial_sync().unwrap();
+    assert!(claude.path().join("deploy").exists());
+
+    // Personal skills keep syncing meanwhile
+    write_skill(&personal, "fresh", "new", &[]);
+    sync.handle_batch(&[personal.join("fresh")]);
+    assert!(claude.path().join("fresh/SKILL.md").exists());
+}
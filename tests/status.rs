@@ -0,0 +1,65 @@
+use skillsync::{Destination, DestinationType, SkillSync, StatusFile};
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn status_file_tracks_syncs_and_errors() {
+    let source = TempDir::new().unwrap();
+    let claude = TempDir::new().unwrap();
+    let toml = TempDir::new().unwrap();
+    let state = TempDir::new().unwrap();
+    let status_path = state.path().join("status.json");
+
+    let skill = source.path().join("good");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(skill.join("SKILL.md"), "Body").unwrap();
+
+    let sync = SkillSync::new(
+        source.path().to_path_buf(),
+        vec![
+            Destination::new(claude.path().to_path_buf(), DestinationType::ClaudeStyle),
+            Destination::new(toml.path().to_path_buf(), DestinationType::GeminiToml),
+        ],
+    )
+    .with_status(StatusFile::new(status_path.clone()));
+    sync.initial_sync().unwrap();
+
+    let status = StatusFile::read(&status_path).unwrap();
+    assert_eq!(status.pid, std::process::id());
+    assert_eq!(status.last_sync_kind.as_deref(), Some("initial"));
+    assert!(status.last_successful_sync.is_some());
+    assert_eq!(status.destinations[&claude.path().display().to_string()], 1);
+    assert_eq!(status.destinations[&toml.path().display().to_string()], 1);
+    assert!(status.errors.is_empty());
+
+    // A file that is not valid UTF-8 cannot be rendered
+    let broken = source.path().join("broken");
+    fs::create_dir_all(&broken).unwrap();
+    fs::write(broken.join("SKILL.md"), [0xff, 0xfe, 0x00]).unwrap();
+    sync.handle_batch(&[broken.join("SKILL.md")]);
+
+    let status = StatusFile::read(&status_path).unwrap();
+    assert_eq!(status.last_sync_kind.as_deref(), Some("initial"));
+    let key = broken.display().to_string();
+    assert!(status.errors[&key].error.contains("Failed to read"));
+
+    // A reconcile that still cannot render it is not a successful pass
+    sync.reconcile().unwrap();
+    let status = StatusFile::read(&status_path).unwrap();
+    assert_eq!(status.last_sync_kind.as_deref(), Some("initial"));
+    assert!(status.errors.contains_key(&key));
+
+    // A reconcile that renders the repaired skill clears its error
+    fs::write(broken.join("SKILL.md"), "Fixed").unwrap();
+    sync.reconcile().unwrap();
+    let status = StatusFile::read(&status_path).unwrap();
+    assert!(status.errors.is_empty());
+    assert_eq!(status.last_sync_kind.as_deref(), Some("reconcile"));
+
+    sync.handle_batch(&[broken.join("SKILL.md")]);
+
+    let status = StatusFile::read(&status_path).unwrap();
+    assert!(status.errors.is_empty());
+    assert_eq!(status.last_sync_kind.as_deref(), Some("batch"));
+    assert_eq!(status, sync.status().unwrap().snapshot());
+}
@@ -0,0 +1,53 @@
+use skillsync::{Destination, DestinationType, SkillSync};
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn frontmatter_targets_limit_and_clean_destinations() {
+    let source = TempDir::new().unwrap();
+    let cursor = TempDir::new().unwrap();
+    let codex = TempDir::new().unwrap();
+    let commands = TempDir::new().unwrap();
+    let sync = SkillSync::new(
+        source.path().to_path_buf(),
+        vec![
+            Destination::new(cursor.path().to_path_buf(), DestinationType::ClaudeStyle)
+                .with_tool("cursor"),
+            Destination::new(codex.path().to_path_buf(), DestinationType::ClaudeStyle)
+                .with_tool("codex"),
+            Destination::new(commands.path().to_path_buf(), DestinationType::GeminiToml)
+                .with_tool("gemini"),
+        ],
+    );
+
+    let skill = source.path().join("review");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(
+        skill.join("SKILL.md"),
+        "---\nname: review\nskillsync: { exclude: [cursor] }\n---\nBody\n",
+    )
+    .unwrap();
+    fs::write(skill.join("notes.md"), "notes\n").unwrap();
+
+    sync.initial_sync().unwrap();
+    assert!(!cursor.path().join("review").exists());
+    assert!(codex.path().join("review/notes.md").exists());
+    assert!(commands.path().join("review.toml").exists());
+
+    // Narrowing the skill removes what it was already synced to
+    fs::write(
+        skill.join("SKILL.md"),
+        "---\nname: review\nskillsync:\n  only: [cursor]\n---\nBody\n",
+    )
+    .unwrap();
+    sync.handle_batch(&[skill.join("SKILL.md")]);
+    assert!(cursor.path().join("review/notes.md").exists());
+    assert!(!codex.path().join("review").exists());
+    assert!(!commands.path().join("review.toml").exists());
+
+    // A periodic reconcile does not bring excluded outputs back
+    fs::create_dir_all(codex.path().join("review")).unwrap();
+    sync.reconcile().unwrap();
+    assert!(!codex.path().join("review").exists());
+    assert!(!commands.path().join("review.toml").exists());
+}
@@ -0,0 +1,81 @@
+use skillsync::{Destination, DestinationType, SkillSync, StatusFile};
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn templated_skills_render_per_destination() {
+    let source = TempDir::new().unwrap();
+    let home = TempDir::new().unwrap();
+    let cursor = TempDir::new().unwrap();
+    let commands = TempDir::new().unwrap();
+    let state = TempDir::new().unwrap();
+    let status_path = state.path().join("status.json");
+
+    let skill = source.path().join("deploy");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(
+        skill.join("SKILL.md"),
+        "---
+name: deploy
+description: Deploy
+skillsync: { template: true }
+---
+
+Config lives in {{ home }}/.deploy.
+{% if tool == \"gemini\" %}
+Run it as /{{ skill }} in {{ tool.name }}.
+{% else %}
+Ask {{ tool.name }} to deploy.
+{% endif %}
+",
+    )
+    .unwrap();
+
+    let broken = source.path().join("broken");
+    fs::create_dir_all(&broken).unwrap();
+    fs::write(
+        broken.join("SKILL.md"),
+        "---\nname: broken\nskillsync: { template: true }\n---\n{{ tool.version }}\n",
+    )
+    .unwrap();
+
+    let sync = SkillSync::new(
+        source.path().to_path_buf(),
+        vec![
+            Destination::new(cursor.path().to_path_buf(), DestinationType::ClaudeStyle)
+                .with_tool("cursor"),
+            Destination::new(commands.path().to_path_buf(), DestinationType::GeminiToml)
+                .with_tool("gemini"),
+        ],
+    )
+    .with_home(home.path().to_path_buf())
+    .with_status(StatusFile::new(status_path.clone()));
+    sync.initial_sync().unwrap();
+
+    let md = fs::read_to_string(cursor.path().join("deploy/SKILL.md")).unwrap();
+    // The targeting key is for skillsync only and never reaches a tool
+    assert!(md.contains("description: Deploy\n---\n"));
+    assert!(!md.contains("skillsync"));
+    assert!(md.ends_with(&format!(
+        "\nConfig lives in {}/.deploy.\nAsk Cursor to deploy.\n",
+        home.path().display()
+    )));
+
+    let toml: toml::Value =
+        toml::from_str(&fs::read_to_string(commands.path().join("deploy.toml")).unwrap()).unwrap();
+    let prompt = toml.get("prompt").and_then(|p| p.as_str()).unwrap();
+    assert!(prompt.contains("Run it as /deploy in Gemini CLI.\n"));
+    assert!(!prompt.contains("Ask"));
+
+    // The broken template is reported with its line and skipped
+    assert!(!cursor.path().join("broken").exists());
+    let status = StatusFile::read(&status_path).unwrap();
+    let error = &status.errors[&broken.display().to_string()];
+    assert!(
+        error
+            .error
+            .contains("line 5: unknown template variable `tool.version`"),
+        "{}",
+        error.error
+    );
+}
@@ -0,0 +1,159 @@
+use skillsync::config::MissingToolPolicy;
+use skillsync::preflight;
+use skillsync::Config;
+use std::fs;
+use tempfile::TempDir;
+
+fn write_config(home: &TempDir, text: &str) {
+    let app_dir = home.path().join("skillsync");
+    fs::create_dir_all(&app_dir).unwrap();
+    fs::write(app_dir.join("config.toml"), text).unwrap();
+}
+
+#[test]
+fn missing_gemini_disables_only_gemini_destinations() {
+    let home = TempDir::new().unwrap();
+    let config = Config::for_home(home.path()).unwrap();
+    assert_eq!(config.tools.on_missing, MissingToolPolicy::Disable);
+
+    let no_gemini = |spec: &preflight::ToolSpec| spec.id != "gemini";
+    let enabled = preflight::enabled_destinations(&config, no_gemini);
+
+    assert_eq!(enabled.len(), 3);
+    assert!(enabled
+        .iter()
+        .all(|dest| dest.tool.as_deref() != Some("gemini")));
+    // Antigravity lives under ~/.gemini but does not need the CLI
+    assert!(enabled
+        .iter()
+        .any(|dest| dest.base_path.ends_with(".gemini/antigravity/skills")));
+    assert_eq!(preflight::missing_tools(&config, no_gemini).len(), 2);
+
+    // Projects follow the same policy
+    let project: Vec<_> = preflight::enabled_project_destinations(&config, no_gemini)
+        .into_iter()
+        .map(|dest| dest.base_path)
+        .collect();
+    assert_eq!(
+        project,
+        vec![
+            std::path::PathBuf::from(".codex/skills"),
+            std::path::PathBuf::from(".cursor/skills")
+        ]
+    );
+}
+
+#[test]
+fn warn_policy_keeps_every_destination() {
+    let home = TempDir::new().unwrap();
+    write_config(&home, "[tools]\non_missing = \"warn\"\n");
+    let config = Config::for_home(home.path()).unwrap();
+
+    let enabled = preflight::enabled_destinations(&config, |_| false);
+    assert_eq!(enabled, config.destinations);
+}
+
+#[test]
+fn configured_destinations_declare_or_infer_their_tool() {
+    let home = TempDir::new().unwrap();
+    write_config(
+        &home,
+        r#"
+[[destinations]]
+path = "~/.codex/skills"
+type = "claude-style"
+
+[[destinations]]
+path = "~/work/prompts"
+type = "gemini-toml"
+tool = "gemini"
+"#,
+    );
+    let config = Config::for_home(home.path()).unwrap();
+
+    let tools: Vec<_> = config
+        .destinations
+        .iter()
+        .map(|dest| preflight::destination_tool(&config.home, dest).map(|spec| spec.id))
+        .collect();
+    assert_eq!(tools, vec![Some("codex"), Some("gemini")]);
+
+    let enabled = preflight::enabled_destinations(&config, |spec| spec.id == "gemini");
+    assert_eq!(enabled.len(), 1);
+    assert!(enabled[0].base_path.ends_with("work/prompts"));
+
+    write_config(
+        &home,
+        "[[destinations]]\npath = \"~/x\"\ntype = \"claude-style\"\ntool = \"vim\"\n",
+    );
+    assert!(Config::for_home(home.path()).is_err());
+}
+
+#[test]
+fn old_tool_versions_are_reported() {
+    use skillsync::version::Version;
+
+    let home = TempDir::new().unwrap();
+    let config = Config::for_home(home.path()).unwrap();
+
+    let warnings = preflight::version_warnings(&config.home, &config.destinations, |spec| {
+        (spec.id == "gemini").then_some(Version::new(0, 22, 1))
+    });
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("Gemini CLI 0.22.1 is outside >= 0.23.0"));
+    assert!(warnings[0].contains(".gemini/skills"));
+
+    let warnings = preflight::version_warnings(&config.home, &config.destinations, |_| {
+        Some(Version::new(0, 23, 0))
+    });
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn renderers_without_a_declared_range_accept_every_version() {
+    use skillsync::version::Version;
+    use skillsync::DestinationType;
+
+    assert!(DestinationType::GeminiToml.support("gemini").is_none());
+    assert!(DestinationType::ClaudeStyle.support("codex").is_none());
+    assert!(DestinationType::ClaudeStyle.support("cursor").is_none());
+
+    let home = TempDir::new().unwrap();
+    let config = Config::for_home(home.path()).unwrap();
+
+    // Only the declared pair, Gemini CLI reading SKILL.md, warns for an ancient version
+    let warnings = preflight::version_warnings(&config.home, &config.destinations, |_| {
+        Some(Version::new(0, 0, 1))
+    });
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains(".gemini/skills"));
+}
+
+#[test]
+fn version_detection_parses_output_and_times_out() {
+    use skillsync::version::Version;
+    use std::os::unix::fs::PermissionsExt;
+    use std::time::{Duration, Instant};
+
+    let dir = TempDir::new().unwrap();
+    let script = |name: &str, body: &str| {
+        let path = dir.path().join(name);
+        fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    };
+
+    let fast = script("fast", "echo \"codex-cli $1 0.46.1\"");
+    assert_eq!(
+        preflight::detect_version(&fast, &["--version"], Duration::from_secs(5)),
+        Some(Version::new(0, 46, 1))
+    );
+
+    let slow = script("slow", "sleep 10");
+    let started = Instant::now();
+    assert_eq!(
+        preflight::detect_version(&slow, &["--version"], Duration::from_millis(200)),
+        None
+    );
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
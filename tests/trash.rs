@@ -0,0 +1,188 @@
+mod common;
+
+use common::{write, Fixture};
+use skillsync::manifest::Manifest;
+use skillsync::trash::{Mirror, Trash};
+use skillsync::{Destination, DestinationType, SkillSync};
+use std::fs;
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[test]
+fn orphans_are_moved_to_trash_and_restorable() {
+    let fixture = Fixture::new();
+    let orphan_dir = fixture.claude.path().join("old-skill");
+    let orphan_toml = fixture.toml.path().join("old-skill.toml");
+    write(&orphan_dir.join("SKILL.md"), "Old skill");
+    write(&orphan_toml, "prompt = 'x'");
+
+    let trash = fixture.trash();
+    fixture
+        .sync()
+        .with_trash(trash.clone())
+        .initial_sync()
+        .unwrap();
+
+    assert!(!orphan_dir.exists());
+    assert!(!orphan_toml.exists());
+
+    let snapshots = trash.snapshots().unwrap();
+    assert_eq!(snapshots.len(), 1);
+    assert_eq!(snapshots[0].entries.len(), 2);
+
+    let mirrors = [Mirror {
+        source: fixture.source.path().to_path_buf(),
+        destinations: fixture.destinations(),
+    }];
+    let restored = trash
+        .restore(&snapshots[0], Some("old-skill"), &mirrors)
+        .unwrap();
+    assert_eq!(restored.len(), 2);
+    assert_eq!(
+        fs::read_to_string(orphan_dir.join("SKILL.md")).unwrap(),
+        "Old skill"
+    );
+    assert!(orphan_toml.exists());
+
+    // The Claude-style copy seeds the source so the daemon keeps it
+    assert_eq!(
+        fs::read_to_string(fixture.source.path().join("old-skill").join("SKILL.md")).unwrap(),
+        "Old skill"
+    );
+    assert!(trash.snapshots().unwrap().is_empty());
+}
+
+#[test]
+fn restore_of_unknown_skill_fails() {
+    let fixture = Fixture::new();
+    write(&fixture.claude.path().join("gone").join("SKILL.md"), "Gone");
+
+    let trash = fixture.trash();
+    fixture
+        .sync()
+        .with_trash(trash.clone())
+        .initial_sync()
+        .unwrap();
+
+    let snapshot = trash.snapshot("latest").unwrap();
+    let mirrors = [Mirror {
+        source: fixture.source.path().to_path_buf(),
+        destinations: fixture.destinations(),
+    }];
+    assert!(trash.restore(&snapshot, Some("other"), &mirrors).is_err());
+    assert_eq!(trash.snapshot("latest").unwrap().entries.len(), 1);
+}
+
+#[test]
+fn restored_command_rebuilds_source_and_is_owned_again() {
+    let fixture = Fixture::new();
+    let project = TempDir::new().unwrap();
+
+    let skills = project.path().join(".claude").join("skills");
+    let commands = project.path().join(".gemini").join("commands");
+    write(
+        &skills.join("deploy").join("SKILL.md"),
+        "---\nname: deploy\ndescription: Ship to prod\n---\n\nDeploy steps",
+    );
+
+    let destinations = vec![Destination::new(
+        commands.clone(),
+        DestinationType::GeminiToml,
+    )];
+    let trash = fixture.trash();
+    let manifest_file = fixture.state.path().join("project.json");
+    let sync = SkillSync::new(skills.clone(), destinations.clone())
+        .with_manifest(Manifest::load(manifest_file.clone()))
+        .with_trash(trash.clone());
+    sync.initial_sync().unwrap();
+
+    fs::remove_dir_all(skills.join("deploy")).unwrap();
+    sync.reconcile().unwrap();
+    assert!(!commands.join("deploy.toml").exists());
+
+    // The home mirror must not claim the project's entry
+    let mirrors = [
+        Mirror {
+            source: fixture.source.path().to_path_buf(),
+            destinations: fixture.destinations(),
+        },
+        Mirror {
+            source: skills.clone(),
+            destinations,
+        },
+    ];
+    let restored = trash
+        .restore_snapshot("latest", Some("deploy"), &mirrors)
+        .unwrap();
+    assert_eq!(restored.paths, vec![commands.join("deploy.toml")]);
+    sync.adopt(&restored.paths);
+
+    let rebuilt = fs::read_to_string(skills.join("deploy").join("SKILL.md")).unwrap();
+    assert!(rebuilt.contains("description: \"Ship to prod\""));
+    assert!(rebuilt.contains("Deploy steps"));
+    assert!(!fixture.source.path().join("deploy").exists());
+
+    // A fresh sync from the manifest file keeps the restored command current
+    let sync = SkillSync::new(skills.clone(), mirrors[1].destinations.clone())
+        .with_manifest(Manifest::load(manifest_file));
+    sync.reconcile().unwrap();
+    fs::write(
+        skills.join("deploy").join("SKILL.md"),
+        "---\nname: deploy\ndescription: Ship it\n---\n\nNew steps",
+    )
+    .unwrap();
+    sync.reconcile().unwrap();
+    assert!(fs::read_to_string(commands.join("deploy.toml"))
+        .unwrap()
+        .contains("New steps"));
+}
+
+#[test]
+fn prune_keeps_newest_snapshots() {
+    let state_dir = TempDir::new().unwrap();
+    let trash = Trash::new(state_dir.path().join("trash"), 2, Duration::from_secs(3600));
+
+    for id in [
+        "2026-01-01T00-00-00Z",
+        "2026-01-02T00-00-00Z",
+        "2026-01-03T00-00-00Z",
+    ] {
+        fs::create_dir_all(trash.root().join(id)).unwrap();
+    }
+
+    assert_eq!(trash.prune().unwrap(), 1);
+    let ids: Vec<String> = trash
+        .snapshots()
+        .unwrap()
+        .into_iter()
+        .map(|s| s.id)
+        .collect();
+    assert_eq!(ids, vec!["2026-01-02T00-00-00Z", "2026-01-03T00-00-00Z"]);
+}
+
+#[test]
+fn reconcile_prunes_old_snapshots() {
+    let fixture = Fixture::new();
+    let trash = Trash::new(
+        fixture.state.path().join("trash"),
+        1,
+        Duration::from_secs(3600),
+    );
+    for id in ["2026-01-01T00-00-00Z", "2026-01-02T00-00-00Z"] {
+        fs::create_dir_all(trash.root().join(id)).unwrap();
+    }
+
+    fixture
+        .sync()
+        .with_trash(trash.clone())
+        .reconcile()
+        .unwrap();
+
+    let ids: Vec<String> = trash
+        .snapshots()
+        .unwrap()
+        .into_iter()
+        .map(|s| s.id)
+        .collect();
+    assert_eq!(ids, vec!["2026-01-02T00-00-00Z"]);
+}